@@ -0,0 +1,78 @@
+// Ticks per second of the dense board at sizes from 16x16 to
+// 2048x2048, as a baseline to compare optimisations against.
+//
+//     cargo bench --bench sweep
+//     cargo bench --bench sweep -- --csv
+
+use std::time::{Duration, Instant};
+
+use gameoflife_rs::board::Board;
+
+const WARM_UP: Duration = Duration::from_secs(1);
+const MEASUREMENT: Duration = Duration::from_secs(3);
+
+struct Sample {
+    width: usize,
+    height: usize,
+    ticks_per_second: f64,
+    ns_per_cell_per_tick: f64,
+}
+
+fn measure(side: usize) -> Sample {
+    // Ticks a half-full soup for WARM_UP, then counts the ticks that
+    // fit in MEASUREMENT
+    let mut board = Board::with_size(side, side);
+    board.set_history_depth(0);
+    board.reset_random(0, 0.5);
+
+    let start = Instant::now();
+    while start.elapsed() < WARM_UP {
+        board.tick();
+    }
+
+    let mut ticks = 0u64;
+    let start = Instant::now();
+    while start.elapsed() < MEASUREMENT {
+        board.tick();
+        ticks += 1;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    Sample {
+        width: side,
+        height: side,
+        ticks_per_second: ticks as f64 / elapsed,
+        ns_per_cell_per_tick: elapsed * 1e9 / (ticks as f64 * (side * side) as f64),
+    }
+}
+
+fn main() {
+    // Cargo passes --bench to every bench target, so anything other
+    // than --csv is ignored
+    let csv = std::env::args().any(|arg| arg == "--csv");
+
+    if csv {
+        println!("width,height,ticks_per_second,ns_per_cell_per_tick");
+    } else {
+        println!(
+            "{:>6} {:>6} {:>16} {:>20}",
+            "width", "height", "ticks/s", "ns/cell/tick"
+        );
+    }
+    let mut side = 16;
+    while side <= 2048 {
+        let sample = measure(side);
+        if csv {
+            println!(
+                "{},{},{:.1},{:.3}",
+                sample.width, sample.height, sample.ticks_per_second, sample.ns_per_cell_per_tick
+            );
+        } else {
+            println!(
+                "{:>6} {:>6} {:>16.1} {:>20.3}",
+                sample.width, sample.height, sample.ticks_per_second, sample.ns_per_cell_per_tick
+            );
+        }
+        side *= 2;
+    }
+}
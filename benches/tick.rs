@@ -0,0 +1,170 @@
+// Benchmarks for advancing the dense board by a generation.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::StdRng, SeedableRng};
+
+use gameoflife_rs::{
+    bitboard::BitBoard,
+    board::{Board, Parallelism, BOARD_HEIGHT, BOARD_WIDTH},
+    life::{randomize, Life},
+};
+
+fn random_board() -> Board {
+    // Without undo history, so ticks aren't also timing the snapshot
+    // each one takes, which the other engines don't
+    let mut board = Board::new();
+    board.set_history_depth(0);
+    seed(&mut board);
+    board
+}
+
+fn seed(life: &mut dyn Life) {
+    // Same half-full soup on every engine
+    let (width, height) = ((BOARD_WIDTH - 1) as i64, (BOARD_HEIGHT - 1) as i64);
+    randomize(
+        life,
+        (1, 1),
+        width,
+        height,
+        0.5,
+        &mut StdRng::seed_from_u64(0),
+    );
+}
+
+fn tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick");
+
+    group.bench_function("double buffered", |b| {
+        let mut board = random_board();
+        b.iter(|| black_box(&mut board).tick())
+    });
+
+    // How tick used to work: collect the cells to flip, then look
+    // each one up again to flip it
+    group.bench_function("flip list", |b| {
+        let mut board = random_board();
+        b.iter(|| {
+            let board = black_box(&mut board);
+            for coords in board.get_cells_to_flip().iter() {
+                board.get_cell_mut(*coords).unwrap().flip();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn engines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("engines");
+
+    group.bench_function("dense", |b| {
+        let mut board = random_board();
+        b.iter(|| black_box(&mut board).tick())
+    });
+
+    group.bench_function("bitpacked", |b| {
+        let mut board = BitBoard::new(BOARD_WIDTH - 1, BOARD_HEIGHT - 1);
+        seed(&mut board);
+        b.iter(|| black_box(&mut board).tick())
+    });
+
+    group.bench_function("bitpacked scalar", |b| {
+        let mut board = BitBoard::new(BOARD_WIDTH - 1, BOARD_HEIGHT - 1);
+        seed(&mut board);
+        b.iter(|| black_box(&mut board).tick_scalar())
+    });
+
+    group.finish();
+}
+
+fn board_ops(c: &mut Criterion) {
+    // The dense board's hot paths on the default board and square ones
+    // of several sizes. Ticks are reported in generations per second,
+    // and neighbour counts in cells per second.
+    let mut sizes = vec![("default".to_string(), random_board())];
+    for &side in [50, 200, 500].iter() {
+        let mut board = Board::with_size(side, side);
+        board.set_history_depth(0);
+        board.reset_random(0, 0.5);
+        sizes.push((format!("{}x{}", side, side), board));
+    }
+
+    let mut group = c.benchmark_group("board");
+    for (size, board) in sizes.iter() {
+        group.throughput(Throughput::Elements(
+            (board.width() * board.height()) as u64,
+        ));
+        group.bench_with_input(
+            BenchmarkId::new("count_adjacent_alive", size),
+            board,
+            |b, board| {
+                b.iter(|| {
+                    let mut total = 0;
+                    for x in 1..=board.width() {
+                        for y in 1..=board.height() {
+                            total += board.count_adjacent_alive(black_box((x, y)));
+                        }
+                    }
+                    total
+                })
+            },
+        );
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(
+            BenchmarkId::new("get_cells_to_flip", size),
+            board,
+            |b, board| b.iter(|| black_box(board).get_cells_to_flip()),
+        );
+        group.bench_with_input(BenchmarkId::new("tick", size), board, |b, board| {
+            let mut board = board.clone();
+            b.iter(|| black_box(&mut board).tick())
+        });
+    }
+    group.finish();
+}
+
+fn big_board() -> Board {
+    // A board big enough for threads to pay off
+    let mut board = Board::with_size(1000, 1000);
+    board.set_history_depth(0);
+    board.reset_random(0, 0.5);
+    board
+}
+
+fn thread_counts() -> [(&'static str, usize); 3] {
+    let all = std::thread::available_parallelism().map_or(1, |n| n.get());
+    [("1 thread", 1), ("2 threads", 2), ("all cores", all)]
+}
+
+fn strips(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strips");
+    for &(name, threads) in thread_counts().iter() {
+        let mut board = big_board();
+        board.set_parallelism(Parallelism::Strips(Some(threads)));
+        group.bench_function(name, |b| b.iter(|| black_box(&mut board).tick()));
+    }
+    group.finish();
+}
+
+#[cfg(feature = "parallel")]
+fn rayon(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rayon");
+    for &(name, threads) in thread_counts().iter() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let mut board = big_board();
+        board.set_parallelism(Parallelism::Rayon);
+        group.bench_function(name, |b| {
+            b.iter(|| pool.install(|| black_box(&mut board).tick()))
+        });
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "parallel"))]
+criterion_group!(benches, tick, engines, board_ops, strips);
+#[cfg(feature = "parallel")]
+criterion_group!(benches, tick, engines, board_ops, strips, rayon);
+criterion_main!(benches);
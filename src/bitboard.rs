@@ -0,0 +1,317 @@
+// Bounded board that packs each row into 64-bit words, one bit per cell.
+
+use crate::life::Life;
+
+const WORD_BITS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitBoard {
+    width: usize,
+    height: usize,
+    // rows[y][w] holds cells w * 64 to w * 64 + 63 of row y, lowest bit
+    // first. Bits past the right edge of the board are always zero.
+    rows: Vec<Vec<u64>>,
+    // Same shape as `rows`, written by each tick and then swapped in
+    next: Vec<Vec<u64>>,
+}
+
+impl BitBoard {
+    pub fn new(width: usize, height: usize) -> BitBoard {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        BitBoard {
+            width,
+            height,
+            rows: vec![vec![0; words_per_row]; height],
+            next: vec![vec![0; words_per_row]; height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        // Whether the cell at zero-based (x, y) is alive
+        self.rows[y][x / WORD_BITS] >> (x % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+        // Sets the cell at zero-based (x, y)
+        let bit = 1 << (x % WORD_BITS);
+        if alive {
+            self.rows[y][x / WORD_BITS] |= bit;
+        } else {
+            self.rows[y][x / WORD_BITS] &= !bit;
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.tick_with(next_word);
+    }
+
+    pub fn tick_scalar(&mut self) {
+        // Same as tick, but counts each cell's neighbours one bit at a
+        // time. Slower; kept as a reference to check tick against.
+        self.tick_with(next_word_scalar);
+    }
+
+    fn tick_with(&mut self, next_word: fn(u64, &[u64; 8]) -> u64) {
+        for y in 0..self.height {
+            for w in 0..self.rows[y].len() {
+                let planes = self.neighbor_planes(y, w);
+                self.next[y][w] = next_word(self.rows[y][w], &planes) & self.word_mask(w);
+            }
+        }
+        std::mem::swap(&mut self.rows, &mut self.next);
+    }
+
+    fn neighbor_planes(&self, y: usize, w: usize) -> [u64; 8] {
+        // Returns eight words lined up with word `w` of row `y`, each
+        // holding one of the eight neighbours of every cell in it:
+        // bit i of a plane is that neighbour of the cell at bit i.
+
+        let (above_w, above, above_e) = self.shifted_row(y.checked_sub(1), w);
+        let (west, _, east) = self.shifted_row(Some(y), w);
+        let (below_w, below, below_e) = self.shifted_row(Some(y + 1), w);
+        [above_w, above, above_e, west, east, below_w, below, below_e]
+    }
+
+    fn shifted_row(&self, y: Option<usize>, w: usize) -> (u64, u64, u64) {
+        // Word `w` of row `y`, along with the same word shifted so each
+        // bit holds its west neighbour and its east neighbour, carrying
+        // bits across from the adjacent words. Rows off the board are dead.
+
+        let row = match y.and_then(|y| self.rows.get(y)) {
+            Some(row) => row,
+            None => return (0, 0, 0),
+        };
+        let word = row[w];
+        let before = if w > 0 { row[w - 1] } else { 0 };
+        let after = row.get(w + 1).copied().unwrap_or(0);
+
+        let west = word << 1 | before >> (WORD_BITS - 1);
+        let east = word >> 1 | after << (WORD_BITS - 1);
+        (west, word, east)
+    }
+
+    fn index(&self, coords: (i64, i64)) -> Option<(usize, usize)> {
+        // Converts Life coordinates into zero-based (x, y),
+        // or None if they are off the board
+        if coords.0 < 1
+            || coords.1 < 1
+            || coords.0 > self.width as i64
+            || coords.1 > self.height as i64
+        {
+            None
+        } else {
+            Some(((coords.0 - 1) as usize, (coords.1 - 1) as usize))
+        }
+    }
+
+    fn word_mask(&self, w: usize) -> u64 {
+        // Bits of word `w` that are inside the board
+        let remaining = self.width - w * WORD_BITS;
+        if remaining >= WORD_BITS {
+            u64::MAX
+        } else {
+            (1 << remaining) - 1
+        }
+    }
+}
+
+fn next_word(word: u64, planes: &[u64; 8]) -> u64 {
+    // Applies Conway's rules to all 64 cells of `word` at once. The
+    // eight planes are added together with bitwise adders, giving each
+    // cell's neighbour count as bits of weight 1, 2 and 4 spread across
+    // three words, without ever looking at a single cell.
+
+    let [a, b, c, d, e, f, g, h] = *planes;
+    let (sum_abc, carry_abc) = full_add(a, b, c);
+    let (sum_def, carry_def) = full_add(d, e, f);
+    let (sum_gh, carry_gh) = (g ^ h, g & h);
+
+    let (ones, carry_ones) = full_add(sum_abc, sum_def, sum_gh);
+    let (twos, carry_twos) = full_add(carry_abc, carry_def, carry_gh);
+    let (twos, carry_twos_ones) = (twos ^ carry_ones, twos & carry_ones);
+    // Set for any count of 4 or more, where every cell is dead
+    let fours = carry_twos | carry_twos_ones;
+
+    // Alive next generation with a count of 3, or 2 if already alive
+    twos & !fours & (ones | word)
+}
+
+fn full_add(a: u64, b: u64, c: u64) -> (u64, u64) {
+    // Adds three words bit by bit, returning the sum and carry words
+    let partial = a ^ b;
+    (partial ^ c, a & b | partial & c)
+}
+
+fn next_word_scalar(word: u64, planes: &[u64; 8]) -> u64 {
+    // Applies Conway's rules to all 64 cells of `word`, counting
+    // each cell's neighbours from the eight planes one bit at a time.
+
+    let mut next = 0;
+    for i in 0..WORD_BITS {
+        let num_adjacent_alive: u32 = planes.iter().map(|plane| (plane >> i & 1) as u32).sum();
+        let alive = word >> i & 1 == 1;
+        if matches!((alive, num_adjacent_alive), (true, 2) | (_, 3)) {
+            next |= 1 << i;
+        }
+    }
+    next
+}
+
+impl Life for BitBoard {
+    // Coordinates run from 1 to width/height inclusive,
+    // matching the dense board.
+
+    fn step(&mut self) {
+        self.tick();
+    }
+
+    fn is_alive_at(&self, coords: (i64, i64)) -> bool {
+        match self.index(coords) {
+            Some((x, y)) => self.get(x, y),
+            None => false,
+        }
+    }
+
+    fn set_alive_at(&mut self, coords: (i64, i64), alive: bool) {
+        if let Some((x, y)) = self.index(coords) {
+            self.set(x, y, alive);
+        }
+    }
+
+    fn population(&self) -> usize {
+        self.rows
+            .iter()
+            .flatten()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::life::randomize;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn assert_same_cells(bits: &BitBoard, board: &Board) {
+        for x in 1..=board.width() as i64 {
+            for y in 1..=board.height() as i64 {
+                assert_eq!(
+                    bits.is_alive_at((x, y)),
+                    board.is_alive_at((x, y)),
+                    "cell ({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bitboard_get_set() {
+        let mut bits = BitBoard::new(130, 3);
+        for &x in [0, 63, 64, 129].iter() {
+            bits.set(x, 1, true);
+            assert!(bits.get(x, 1));
+        }
+        assert_eq!(bits.population(), 4);
+        bits.set(64, 1, false);
+        assert!(!bits.get(64, 1));
+        assert_eq!(bits.population(), 3);
+    }
+
+    #[test]
+    fn bitboard_blinker_across_words() {
+        // Horizontal blinker straddling the boundary between two words
+        let mut bits = BitBoard::new(100, 5);
+        for &x in [62, 63, 64].iter() {
+            bits.set(x, 2, true);
+        }
+        bits.tick();
+        for &y in [1, 2, 3].iter() {
+            assert!(bits.get(63, y));
+        }
+        assert_eq!(bits.population(), 3);
+        bits.tick();
+        for &x in [62, 63, 64].iter() {
+            assert!(bits.get(x, 2));
+        }
+    }
+
+    #[test]
+    fn bitboard_next_word_matches_scalar() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let word = rng.gen();
+            let mut planes = [0; 8];
+            for plane in planes.iter_mut() {
+                // Mix of sparse and dense planes so every count from 0 to 8 turns up
+                *plane = rng.gen::<u64>() & rng.gen::<u64>() | rng.gen::<u64>() & rng.gen::<u64>();
+            }
+            assert_eq!(next_word(word, &planes), next_word_scalar(word, &planes));
+        }
+    }
+
+    #[test]
+    fn bitboard_tick_matches_scalar() {
+        for &width in [1, 63, 64, 65, 100, 128, 200].iter() {
+            let mut swar = BitBoard::new(width, 20);
+            randomize(
+                &mut swar,
+                (1, 1),
+                width as i64,
+                20,
+                0.4,
+                &mut StdRng::seed_from_u64(width as u64),
+            );
+            let mut scalar = swar.clone();
+
+            for _ in 0..50 {
+                swar.tick();
+                scalar.tick_scalar();
+                assert_eq!(swar, scalar, "width {}", width);
+            }
+        }
+    }
+
+    #[test]
+    fn bitboard_matches_dense() {
+        for &seed in [1, 2, 3].iter() {
+            let mut board = Board::new();
+            let mut bits = BitBoard::new(board.width(), board.height());
+            let (width, height) = (board.width() as i64, board.height() as i64);
+            randomize(
+                &mut board,
+                (1, 1),
+                width,
+                height,
+                0.4,
+                &mut StdRng::seed_from_u64(seed),
+            );
+            randomize(
+                &mut bits,
+                (1, 1),
+                width,
+                height,
+                0.4,
+                &mut StdRng::seed_from_u64(seed),
+            );
+
+            for _ in 0..100 {
+                board.tick();
+                bits.tick();
+                assert_eq!(bits.population(), board.population());
+            }
+            assert_same_cells(&bits, &board);
+        }
+    }
+}
@@ -0,0 +1,5217 @@
+// Dense board, with an optional ability to grow as its pattern spreads.
+
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    fmt,
+    fs::File,
+    hash::{Hash, Hasher},
+    io::{self, BufRead, Read, Write},
+    ops::RangeInclusive,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+    write,
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "image")]
+use crate::error::ImageLoadError;
+use crate::{
+    coord::{Coord, NEIGHBOUR_OFFSETS},
+    cycle::CycleDetector,
+    error::{BoardError, GolError, OutOfBoundsError, PatternTooLargeError, SizeMismatchError},
+    heatmap::HeatMap,
+    history::{BoardSnapshot, History},
+    life::{self, randomize, Life},
+    rule::Rule,
+    stats::{self, TickStats},
+};
+
+pub const BOARD_WIDTH: usize = 125 + 1;
+pub const BOARD_HEIGHT: usize = 70 + 1;
+
+pub const ALIVE_GLYPH: char = '█';
+pub const DEAD_GLYPH: char = '░';
+
+// Side length, in pixels, of a single cell when the board is rendered as an image.
+const PIXEL_SCALE: usize = 4;
+
+// Number of cells added to a side of a growing board when it runs out of room.
+const GROW_MARGIN: usize = 16;
+
+// Dead cells left around every side of a pattern loaded from a file.
+const PATTERN_MARGIN: usize = 4;
+
+// Longest side of a board read from a file. Anything bigger is refused
+// as InvalidSize, so a corrupted or crafted size can't ask for more
+// memory than there is.
+const MAX_LOADED_SIZE: usize = 8192;
+
+const LIFE106_HEADER: &str = "#Life 1.06";
+
+// First line of a file written by Board::save, followed by the version
+// of the format. Bump the version whenever the layout changes, so older
+// builds refuse newer files rather than misreading them.
+const SAVE_HEADER: &str = "gameoflife-rs board";
+const SAVE_VERSION: u32 = 1;
+
+// First bytes of a file written by Board::write_snapshot, followed by
+// the version of its layout, bumped whenever the layout changes
+const SNAPSHOT_MAGIC: [u8; 4] = *b"GOLB";
+const SNAPSHOT_VERSION: u8 = 1;
+
+// Decides whether a cell is alive next generation, from whether it's
+// alive now and how many alive neighbours it has. Shared between threads
+// when ticking in parallel.
+pub type Transition = Arc<dyn Fn(bool, u32) -> bool + Send + Sync>;
+
+// Bit of a braille character's codepoint that raises the dot for each
+// cell of a 2x4 block, as BRAILLE_DOTS[y][x]
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+// Steps from a cell to the four neighbours it shares an edge with
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+// Called with the board after every tick. Shared rather than boxed so
+// boards stay cloneable, and Send + Sync so they can still be ticked
+// across threads.
+type TickHook = Arc<dyn Fn(&Board) + Send + Sync>;
+
+fn signed(coords: (usize, usize)) -> Coord {
+    // Board coordinates as a Coord, which counts from 0
+    Coord::new(coords.0 as i64 - 1, coords.1 as i64 - 1)
+}
+
+fn check_loaded_size(width: usize, height: usize) -> Result<(), GolError> {
+    // A board read from a file needs at least one cell, and no side
+    // longer than MAX_LOADED_SIZE
+    if width == 0 || height == 0 || width > MAX_LOADED_SIZE || height > MAX_LOADED_SIZE {
+        return Err(GolError::InvalidSize { width, height });
+    }
+    Ok(())
+}
+
+fn zobrist_key(coords: (usize, usize)) -> u64 {
+    // Random-looking key for an alive cell at `coords`, taken from
+    // splitmix64 rather than a stored table so that every board, of
+    // any size, agrees on it. A dead cell contributes nothing.
+
+    let mut z = ((coords.0 as u64) << 32 | coords.1 as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// What a tick did to the cells it wrote: the change to the Zobrist
+// hash, and how many cells were born and how many died
+#[derive(Debug, Clone, Copy, Default)]
+struct Changes {
+    hash: u64,
+    births: usize,
+    deaths: usize,
+}
+
+impl Changes {
+    fn record(&mut self, coords: (usize, usize), alive: bool) {
+        // Notes that the cell at `coords` has just become `alive`
+        self.hash ^= zobrist_key(coords);
+        if alive {
+            self.births += 1;
+        } else {
+            self.deaths += 1;
+        }
+    }
+
+    fn merge(self, other: Changes) -> Changes {
+        Changes {
+            hash: self.hash ^ other.hash,
+            births: self.births + other.births,
+            deaths: self.deaths + other.deaths,
+        }
+    }
+}
+
+// Which surrounding cells count as a cell's neighbours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Neighborhood {
+    // All eight surrounding cells
+    #[default]
+    Moore,
+    // Only the four orthogonally adjacent cells
+    VonNeumann,
+}
+
+impl Neighborhood {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Moore => &NEIGHBOUR_OFFSETS,
+            Neighborhood::VonNeumann => &ORTHOGONAL_OFFSETS,
+        }
+    }
+}
+
+// What the cells just beyond the edges of a board that doesn't wrap
+// count as when their neighbours are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BorderMode {
+    // The same as the background, which is dead unless a B0 rule has
+    // brought it to life
+    #[default]
+    Dead,
+    // Always alive, as if the board were set in an endless sea of
+    // alive cells
+    Alive,
+    // Whatever the nearest cell on the board is, as if the edges were
+    // mirrors
+    Reflective,
+}
+
+// A line or point a board can be made symmetric about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymmetryAxis {
+    // A vertical line down the middle, mirroring left and right
+    Horizontal,
+    // A horizontal line across the middle, mirroring top and bottom
+    Vertical,
+    // The diagonal from the top-left corner
+    Diagonal,
+    // The centre of the board, half a turn round
+    Rotate180,
+}
+
+// An edge of the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+// Cells that would have come to life just beyond an edge of a board
+// that doesn't wrap or grow any further, and so were lost instead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeEvent {
+    pub edge: Edge,
+    // Generation the cells would have been alive in
+    pub generation: u64,
+    // Number of cells lost past this edge
+    pub cells: usize,
+}
+
+// The cells that differ between two boards of the same size, as board
+// coordinates row by row from the top-left
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardDiff {
+    // Alive on the second board but not the first
+    pub births: Vec<(usize, usize)>,
+    // Alive on the first board but not the second
+    pub deaths: Vec<(usize, usize)>,
+}
+
+impl BoardDiff {
+    pub fn compute(before: &Board, after: &Board) -> Result<BoardDiff, BoardError> {
+        // The same as before.diff(after)
+        before.diff(after)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.births.is_empty() && self.deaths.is_empty()
+    }
+
+    pub fn apply(&self, board: &mut Board) -> Result<(), BoardError> {
+        // Brings the births to life and kills the deaths on `board`, so
+        // applying the diff from one board to another to the first
+        // makes it match the second without ticking. The generation
+        // isn't changed. If any cell is off the board, nothing is.
+        let (width, height) = (board.width, board.height);
+        if let Some(&coords) = self
+            .births
+            .iter()
+            .chain(&self.deaths)
+            .find(|&&coords| board.get_cell(coords).is_none())
+        {
+            return Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords,
+                board: (width, height),
+            }));
+        }
+        board.set_cells(&self.births, true)?;
+        board.set_cells(&self.deaths, false)?;
+        Ok(())
+    }
+}
+
+// A board as saved by save_ron. Only the alive cells are stored, as
+// runs along each row, so a pattern can be edited by hand.
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct RonBoard {
+    width: usize,
+    height: usize,
+    generation: u64,
+    // A rulestring such as "B3/S23"
+    rule: String,
+    alive: Vec<Run>,
+}
+
+// A board as serialized by serde, and so by save_json: its settings and
+// generation, and the board coordinates of its alive cells
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedBoard {
+    width: usize,
+    height: usize,
+    rule: Rule,
+    wrap: bool,
+    border_mode: BorderMode,
+    generation: u64,
+    alive: Vec<(usize, usize)>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SavedBoard {
+            width: self.width,
+            height: self.height,
+            rule: self.rule,
+            wrap: self.wrap,
+            border_mode: self.border_mode,
+            generation: self.generation,
+            alive: self.live_cells().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+        // Checks the size and that every cell is on the board, rather
+        // than trusting the file
+        use serde::de::Error;
+
+        let saved = SavedBoard::deserialize(deserializer)?;
+        check_loaded_size(saved.width, saved.height).map_err(D::Error::custom)?;
+        let mut board = Board::with_size(saved.width, saved.height);
+        board.set_rule(saved.rule);
+        board.set_wrap(saved.wrap);
+        board.set_border_mode(saved.border_mode);
+        board
+            .set_cells(&saved.alive, true)
+            .map_err(D::Error::custom)?;
+        board.generation = saved.generation;
+        Ok(board)
+    }
+}
+
+// `length` alive cells in a row, starting at (x, y) in board
+// coordinates and going right
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct Run {
+    x: usize,
+    y: usize,
+    length: usize,
+}
+
+// How a tick of the dense board is shared out between threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parallelism {
+    Serial,
+    // The columns are split into one strip per thread, using scoped
+    // threads from the standard library. None starts as many threads
+    // as std::thread::available_parallelism suggests.
+    Strips(Option<usize>),
+    // Columns are handed out by rayon's thread pool
+    #[cfg(feature = "parallel")]
+    Rayon,
+}
+
+// Just the cell's state. Its coordinates are where it sits in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Cell {
+    pub alive: bool,
+}
+
+impl Cell {
+    pub fn flip(&mut self) {
+        self.alive = !self.alive;
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.alive {
+            write!(f, "{}", ALIVE_GLYPH)
+        } else {
+            write!(f, "{}", DEAD_GLYPH)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Board {
+    // Indexed [x][y]. Column and row 0 are never used, so
+    // valid cells run from 1 to width/height inclusive.
+    board: Vec<Vec<Cell>>,
+    // Same shape as `board`. Each tick writes the next generation
+    // here, then swaps the two, so ticking never allocates.
+    next: Vec<Vec<Cell>>,
+    // Scratch space, same shape as `board`, for marking the cells that
+    // could change this tick. See mark_candidates.
+    candidates: Vec<Vec<bool>>,
+    width: usize,
+    height: usize,
+    // Number of ticks since the board was created or cleared
+    generation: u64,
+    // Added to board coordinates to get the stable coordinates used by
+    // the Life trait, which don't change when the board grows.
+    offset: (i64, i64),
+    // Largest size the board may grow to, or None if it never grows.
+    max_size: Option<(usize, usize)>,
+    // Earlier generations that can be returned to with undo
+    history: History,
+    // Whether ticks are spread across threads. Only worth it for
+    // large boards; small ones tick faster on a single thread.
+    parallelism: Parallelism,
+    rule: Rule,
+    // Used instead of the rule when set
+    transition: Option<Transition>,
+    // Whether the edges wrap around, making the board a torus
+    wrap: bool,
+    neighborhood: Neighborhood,
+    // Ignored on a wrapped board, which has no border
+    border_mode: BorderMode,
+    // Zobrist hash of the alive cells, updated as cells change. None
+    // once get_cell_mut has handed out a cell, since whatever is done
+    // to it can't be seen; the next tick recomputes it.
+    zobrist: Option<u64>,
+    // Number of alive cells, kept up to date and forgotten along with
+    // the Zobrist hash
+    population: Option<usize>,
+    // State of the endless plane of cells beyond the board. Always dead
+    // unless a B0 rule brings the whole plane to life at once.
+    background: bool,
+    // Number of cells that came to life and that died in the last
+    // tick, both 0 before the first one
+    births: usize,
+    deaths: usize,
+    // Number of ticks after which each cell was alive, row by row from
+    // the top-left, or None unless activity is being tracked
+    activity: Option<Vec<u32>>,
+    // Cells lost off the edges since the events were last taken, or
+    // None unless edges are being watched
+    edge_events: Option<Vec<EdgeEvent>>,
+    // Characters Display draws alive and dead cells with
+    glyphs: (char, char),
+    // Called after every tick, in the order they were added. Clones of
+    // the board share them.
+    hooks: Vec<TickHook>,
+}
+
+impl Board {
+    pub fn new() -> Board {
+        Board::with_size(BOARD_WIDTH - 1, BOARD_HEIGHT - 1)
+    }
+
+    pub fn with_size(width: usize, height: usize) -> Board {
+        let cells = vec![vec![Cell { alive: false }; height + 1]; width + 1];
+
+        Board {
+            candidates: vec![vec![false; height + 1]; width + 1],
+            next: cells.clone(),
+            board: cells,
+            width,
+            height,
+            generation: 0,
+            offset: (0, 0),
+            max_size: None,
+            history: History::default(),
+            parallelism: Parallelism::Serial,
+            rule: Rule::default(),
+            transition: None,
+            wrap: false,
+            neighborhood: Neighborhood::Moore,
+            border_mode: BorderMode::Dead,
+            zobrist: Some(0),
+            population: Some(0),
+            background: false,
+            births: 0,
+            deaths: 0,
+            activity: None,
+            edge_events: None,
+            glyphs: (ALIVE_GLYPH, DEAD_GLYPH),
+            hooks: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn offset(&self) -> (i64, i64) {
+        self.offset
+    }
+
+    pub fn set_history_depth(&mut self, max_depth: usize) {
+        // Sets how many generations can be undone. 0 turns history off.
+        self.history.set_max_depth(max_depth);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    pub fn set_parallelism(&mut self, parallelism: Parallelism) {
+        self.parallelism = parallelism;
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        // Also drops any transition function set with set_transition
+        self.rule = rule;
+        self.transition = None;
+    }
+
+    pub fn set_transition<F>(&mut self, transition: F)
+    where
+        F: Fn(bool, u32) -> bool + Send + Sync + 'static,
+    {
+        // Replaces the rule with an arbitrary function of whether a
+        // cell is alive and how many alive neighbours it has, which
+        // returns whether the cell is alive next generation.
+        self.transition = Some(Arc::new(transition));
+    }
+
+    fn next_state(&self, alive: bool, num_adjacent_alive: u32) -> bool {
+        match &self.transition {
+            Some(transition) => transition(alive, num_adjacent_alive),
+            None => self.rule.next_state(alive, num_adjacent_alive),
+        }
+    }
+
+    pub fn background(&self) -> bool {
+        // Whether the cells beyond the board are alive
+        self.background
+    }
+
+    pub fn births(&self) -> usize {
+        // Number of cells that came to life in the last tick
+        self.births
+    }
+
+    pub fn deaths(&self) -> usize {
+        // Number of cells that died in the last tick
+        self.deaths
+    }
+
+    pub fn glyphs(&self) -> (char, char) {
+        // Characters for alive and dead cells when the board is displayed
+        self.glyphs
+    }
+
+    pub fn set_glyphs(&mut self, alive: char, dead: char) {
+        // Changes how the board is displayed, for terminals or fonts
+        // where the block characters look wrong. '*' and ' ' keep to
+        // plain ASCII.
+        self.glyphs = (alive, dead);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        // Whether no cell is alive, on the board or beyond it
+        !self.background
+            && (self.wrap || self.border_mode != BorderMode::Alive)
+            && self.population() == 0
+    }
+
+    pub fn entropy(&self) -> f64 {
+        // Shannon entropy of whether a cell on the board is alive, from
+        // 0 for an empty or full board to 1 for a half-full one
+        stats::entropy(self.population(), self.width * self.height)
+    }
+
+    pub fn is_stable(&self, prev: &Board) -> bool {
+        // Whether the board has the same cells alive as `prev`, such as
+        // the board before the last tick. The hashes are compared first,
+        // so boards that differ are nearly always told apart in O(1).
+        self.background == prev.background
+            && self.zobrist_hash() == prev.zobrist_hash()
+            && self == prev
+    }
+
+    pub fn set_track_activity(&mut self, track: bool) {
+        // Starts counting how often each cell is alive, from zero, or
+        // stops and forgets the counts. Off by default, since it adds
+        // a pass over the board to every tick.
+        self.activity = if track {
+            Some(vec![0; self.width * self.height])
+        } else {
+            None
+        };
+    }
+
+    pub fn activity_map(&self) -> &[u32] {
+        // Number of ticks after which each cell was alive, row by row
+        // from the top-left like to_grid. Counts follow the cells when
+        // the board grows. Empty unless activity is being tracked.
+        self.activity.as_deref().unwrap_or(&[])
+    }
+
+    pub fn reset_activity(&mut self) {
+        if let Some(activity) = &mut self.activity {
+            for count in activity.iter_mut() {
+                *count = 0;
+            }
+        }
+    }
+
+    pub fn set_detect_edges(&mut self, detect: bool) {
+        // Starts or stops recording the cells lost off the edges of a
+        // board with a dead border that doesn't wrap, for telling when
+        // a spaceship has flown off it. Off by default, since it adds a
+        // pass around the edges to every tick. Stopping forgets any
+        // events not taken.
+        self.edge_events = if detect { Some(Vec::new()) } else { None };
+    }
+
+    pub fn take_edge_events(&mut self) -> Vec<EdgeEvent> {
+        // The events recorded since the last call, oldest first. Empty
+        // unless edges are being watched.
+        self.edge_events
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub fn on_tick(&mut self, hook: impl Fn(&Board) + Send + Sync + 'static) {
+        // Calls `hook` with the board at the end of every tick, once the
+        // generation counter has moved on. tick_n calls it for every
+        // generation, so it never skips any while hooks are registered.
+        self.hooks.push(Arc::new(hook));
+    }
+
+    pub fn remove_all_hooks(&mut self) {
+        self.hooks.clear();
+    }
+
+    fn run_hooks(&self) {
+        for hook in &self.hooks {
+            hook(self);
+        }
+    }
+
+    fn detect_edge_crossings(&mut self, generation: u64) {
+        // Looks at the ring of cells just beyond the board, which would
+        // take on a state other than the background's next generation if
+        // the board went on, and records an event for each edge where
+        // any would. The corners count as the top and bottom edges.
+        if self.edge_events.is_none() || self.wrap || self.border_mode != BorderMode::Dead {
+            return;
+        }
+        let background = self.next_background();
+        let lost = |coords| {
+            self.next_state(self.background, self.count_adjacent_alive(coords)) != background
+        };
+        let (right, bottom) = (self.width + 1, self.height + 1);
+        let counts = [
+            (Edge::Top, (0..=right).filter(|&x| lost((x, 0))).count()),
+            (
+                Edge::Bottom,
+                (0..=right).filter(|&x| lost((x, bottom))).count(),
+            ),
+            (Edge::Left, (1..bottom).filter(|&y| lost((0, y))).count()),
+            (
+                Edge::Right,
+                (1..bottom).filter(|&y| lost((right, y))).count(),
+            ),
+        ];
+        if let Some(events) = &mut self.edge_events {
+            events.extend(
+                counts
+                    .iter()
+                    .filter(|&&(_, cells)| cells > 0)
+                    .map(|&(edge, cells)| EdgeEvent {
+                        edge,
+                        generation,
+                        cells,
+                    }),
+            );
+        }
+    }
+
+    fn carry_activity(&mut self, old: &Board) {
+        // Copies the activity counts of `old` over to the same stable
+        // (Life trait) coordinates on this board, which may be a
+        // different size or offset
+        if let (Some(from), Some(to)) = (&old.activity, &mut self.activity) {
+            for x in 1..=old.width {
+                for y in 1..=old.height {
+                    let nx = x as i64 + old.offset.0 - self.offset.0;
+                    let ny = y as i64 + old.offset.1 - self.offset.1;
+                    if (1..=self.width as i64).contains(&nx)
+                        && (1..=self.height as i64).contains(&ny)
+                    {
+                        to[(ny as usize - 1) * self.width + (nx as usize - 1)] =
+                            from[(y - 1) * old.width + (x - 1)];
+                    }
+                }
+            }
+        }
+    }
+
+    fn next_background(&self) -> bool {
+        // The background next generation. Every background cell has
+        // only background cells around it, so they all change together.
+        let neighbours = self.neighborhood.offsets().len() as u32;
+        let num_adjacent_alive = if self.background { neighbours } else { 0 };
+        self.next_state(self.background, num_adjacent_alive)
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    pub fn set_wrap(&mut self, wrap: bool) {
+        // Makes cells on each edge neighbours of the cells on the
+        // opposite edge. A wrapped board never grows.
+        self.wrap = wrap;
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        // Chooses which surrounding cells are counted as neighbours.
+        // The rule still applies to the count, so a Von Neumann board
+        // never sees more than 4.
+        self.neighborhood = neighborhood;
+    }
+
+    pub fn border_mode(&self) -> BorderMode {
+        self.border_mode
+    }
+
+    pub fn set_border_mode(&mut self, border_mode: BorderMode) {
+        // Chooses what the cells beyond the edges count as. Has no
+        // effect while the board wraps.
+        self.border_mode = border_mode;
+    }
+
+    pub fn set_grow(&mut self, max_size: Option<(usize, usize)>) {
+        // Lets the board grow, up to `max_size`, whenever an alive cell
+        // comes within 2 cells of an edge. Pass None to keep its size fixed.
+        self.max_size = max_size;
+    }
+
+    pub fn get_cell(&self, coords: (usize, usize)) -> Option<&Cell> {
+        // Takes a coordinate pair, returns an Option containing
+        // the cell at that coordinate.
+        // If the cell does not exist (example: beyond boundary
+        // of what's allowed on the board), then it contains None
+
+        if coords.0 == 0 || coords.1 == 0 || coords.0 > self.width || coords.1 > self.height {
+            None
+        } else {
+            match self.board.get(coords.0) {
+                Some(column) => match column.get(coords.1) {
+                    Some(cell) => Some(cell),
+                    None => None,
+                },
+                None => None,
+            }
+        }
+    }
+
+    pub fn get_cell_mut(&mut self, coords: (usize, usize)) -> Option<&mut Cell> {
+        // Takes a coordinate pair, returns a mutable Option containing
+        // the cell at that coordinate.
+        // If the cell does not exist (example: beyond boundary of the board),
+        // then it contains None
+        if coords.0 == 0 || coords.1 == 0 || coords.0 > self.width || coords.1 > self.height {
+            return Option::None;
+        }
+        self.zobrist = None;
+        self.population = None;
+
+        match self.board.get_mut(coords.0) {
+            Some(column) => match column.get_mut(coords.1) {
+                Some(cell) => Some(cell),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    pub fn set_alive(&mut self, coords: (usize, usize), alive: bool) -> Result<(), BoardError> {
+        // Makes the cell at `coords` alive or dead, or returns an error
+        // if there's no such cell
+        if self.get_cell(coords).is_none() {
+            return Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords,
+                board: (self.width, self.height),
+            }));
+        }
+        self.set_alive_unchecked(coords, alive);
+        Ok(())
+    }
+
+    pub fn set_cells(
+        &mut self,
+        coords: &[(usize, usize)],
+        alive: bool,
+    ) -> Result<usize, BoardError> {
+        // Makes every cell in `coords` alive or dead, returning how many
+        // weren't already. If any of them is off the board, nothing is
+        // changed and the first such cell is returned as the error.
+        if let Some(&off_board) = coords.iter().find(|&&c| self.get_cell(c).is_none()) {
+            return Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords: off_board,
+                board: (self.width, self.height),
+            }));
+        }
+        let mut changed = 0;
+        for &coords in coords {
+            changed += (self.board[coords.0][coords.1].alive != alive) as usize;
+            self.set_alive_unchecked(coords, alive);
+        }
+        Ok(changed)
+    }
+
+    pub fn place_pattern(
+        &mut self,
+        cells: &[(usize, usize)],
+        origin: (usize, usize),
+    ) -> Result<usize, BoardError> {
+        // Makes alive the cells at `cells`, offsets from the top-left
+        // of the pattern's bounding box, with that corner at `origin`,
+        // returning how many weren't already. Nothing is changed if the
+        // pattern is bigger than the board or runs off its edges.
+        let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        if width > self.width || height > self.height {
+            return Err(BoardError::PatternTooLarge(PatternTooLargeError {
+                pattern: (width, height),
+                board: (self.width, self.height),
+            }));
+        }
+        let coords: Vec<_> = cells
+            .iter()
+            .map(|&(x, y)| (origin.0 + x, origin.1 + y))
+            .collect();
+        self.set_cells(&coords, true)
+    }
+
+    pub fn set_rect(&mut self, x0: usize, y0: usize, x1: usize, y1: usize, alive: bool) -> usize {
+        // Makes every cell in the rectangle with corners (x0, y0) and
+        // (x1, y1), both included and in either order, alive or dead,
+        // returning how many weren't already. Unlike set_cells, the
+        // rectangle is clipped to the board rather than rejected, so
+        // any part of it off the board is ignored.
+        let (xs, ys) = self.clip_rect(x0, y0, x1, y1);
+        let mut changed = 0;
+        for x in xs {
+            for y in ys.clone() {
+                changed += (self.board[x][y].alive != alive) as usize;
+                self.set_alive_unchecked((x, y), alive);
+            }
+        }
+        changed
+    }
+
+    pub fn clear_rect(&mut self, x0: usize, y0: usize, x1: usize, y1: usize) -> usize {
+        // Kills every cell in the rectangle, as set_rect does
+        self.set_rect(x0, y0, x1, y1, false)
+    }
+
+    fn clip_rect(
+        &self,
+        x0: usize,
+        y0: usize,
+        x1: usize,
+        y1: usize,
+    ) -> (RangeInclusive<usize>, RangeInclusive<usize>) {
+        // Columns and rows of the rectangle with corners (x0, y0) and
+        // (x1, y1), in either order, that are on the board
+        (
+            x0.min(x1).max(1)..=x0.max(x1).min(self.width),
+            y0.min(y1).max(1)..=y0.max(y1).min(self.height),
+        )
+    }
+
+    pub fn count_alive_in_region(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> usize {
+        // Number of alive cells in the rectangle, clipped to the board
+        // as set_rect does
+        let (xs, ys) = self.clip_rect(x1, y1, x2, y2);
+        xs.map(|x| ys.clone().filter(|&y| self.board[x][y].alive).count())
+            .sum()
+    }
+
+    pub fn any_alive_in_region(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> bool {
+        // Whether any cell in the rectangle is alive, stopping at the
+        // first one found
+        let (xs, ys) = self.clip_rect(x1, y1, x2, y2);
+        xs.into_iter()
+            .any(|x| ys.clone().any(|y| self.board[x][y].alive))
+    }
+
+    fn set_alive_unchecked(&mut self, coords: (usize, usize), alive: bool) {
+        // Sets a cell that is known to be on the board, keeping the
+        // Zobrist hash and population up to date
+        let cell = &mut self.board[coords.0][coords.1];
+        if cell.alive != alive {
+            cell.alive = alive;
+            if let Some(hash) = &mut self.zobrist {
+                *hash ^= zobrist_key(coords);
+            }
+            if let Some(population) = &mut self.population {
+                if alive {
+                    *population += 1;
+                } else {
+                    *population -= 1;
+                }
+            }
+        }
+    }
+
+    pub fn zobrist_hash(&self) -> u64 {
+        // 64-bit hash of which cells are alive, for spotting repeated
+        // states cheaply. Boards with the same cells in the same places
+        // have the same hash, however they got there. Kept up to date
+        // as cells change, so this is O(1) unless cells have been
+        // edited through get_cell_mut since the last tick.
+        self.zobrist.unwrap_or_else(|| self.compute_zobrist())
+    }
+
+    fn count_population(&self) -> usize {
+        self.board[1..=self.width]
+            .iter()
+            .flat_map(|column| column[1..=self.height].iter())
+            .filter(|cell| cell.alive)
+            .count()
+    }
+
+    fn compute_zobrist(&self) -> u64 {
+        let mut hash = 0;
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                if self.board[x][y].alive {
+                    hash ^= zobrist_key((x, y));
+                }
+            }
+        }
+        hash
+    }
+
+    pub fn count_adjacent_alive(&self, coords: (usize, usize)) -> u32 {
+        // Counts the alive cells among the neighbours of `coords` that
+        // the neighbourhood includes, as get_neighbors reports them.
+        // Neighbours with no state past a dead border take the
+        // background's.
+
+        let (x, y) = coords;
+        let mut num_adjacent_alive = 0;
+
+        // Inside the board and away from its far edges every neighbour
+        // is allocated, and column and row 0 are always dead, so the
+        // neighbours can be indexed without any checks
+        if !self.wrap
+            && !self.background
+            && self.border_mode == BorderMode::Dead
+            && (1..self.width).contains(&x)
+            && (1..self.height).contains(&y)
+        {
+            for &(dx, dy) in self.neighborhood.offsets() {
+                let column = &self.board[x.wrapping_add_signed(dx)];
+                num_adjacent_alive += column[y.wrapping_add_signed(dy)].alive as u32;
+            }
+            return num_adjacent_alive;
+        }
+
+        let offsets = self.neighborhood.offsets();
+        self.get_neighbors(coords)
+            .iter()
+            .zip(NEIGHBOUR_OFFSETS.iter())
+            .filter(|(_, offset)| offsets.contains(offset))
+            .map(|(alive, _)| alive.unwrap_or(self.background) as u32)
+            .sum()
+    }
+
+    pub fn get_neighbors(&self, coords: (usize, usize)) -> [Option<bool>; 8] {
+        // Whether each of the eight cells around `coords` is alive, in
+        // the order NW, N, NE, W, E, SW, S, SE, whatever the
+        // neighbourhood. A neighbour past an edge is looked up where
+        // the wrap or reflective border puts it, and is always alive
+        // past an alive border. Past a dead border it's None, having
+        // no state of its own.
+        signed(coords)
+            .neighbors()
+            .map(|neighbor| match self.onto_board(neighbor) {
+                Some((x, y)) => Some(self.board[x][y].alive),
+                None if !self.wrap && self.border_mode == BorderMode::Alive => Some(true),
+                None => None,
+            })
+    }
+
+    pub fn neighbors(&self, coords: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // Board coordinates of the cells the neighbourhood counts as
+        // neighbours of `coords`. Those past an edge are moved to the
+        // opposite edge on a wrapped board, or onto the nearest cell with
+        // a reflective border, so a cell can come up more than once on a
+        // small board. With a dead or alive border they're left out.
+        let coord = signed(coords);
+        self.neighborhood
+            .offsets()
+            .iter()
+            .filter_map(move |&(dx, dy)| self.onto_board(coord.offset(dx as i64, dy as i64)))
+    }
+
+    pub fn neighbor_counts(&self) -> Vec<Vec<u8>> {
+        // Number of alive neighbours of every cell, as count_adjacent_alive
+        // counts them, laid out as by to_grid so counts[y][x] is for the
+        // cell at (x + 1, y + 1). With the Moore neighbourhood each row is
+        // counted from the sums of three cells down each column, every
+        // sum shared by the three cells beside it, rather than looking up
+        // all eight neighbours of every cell.
+        if self.neighborhood != Neighborhood::Moore {
+            return (1..=self.height)
+                .map(|y| {
+                    (1..=self.width)
+                        .map(|x| self.count_adjacent_alive((x, y)) as u8)
+                        .collect()
+                })
+                .collect();
+        }
+
+        // Whether the cell at (x, y), counting from 0, is alive, or
+        // whatever stands in for it past an edge
+        let border = self.background || (!self.wrap && self.border_mode == BorderMode::Alive);
+        let alive = |x: i64, y: i64| match self.onto_board(Coord::new(x, y)) {
+            Some((x, y)) => self.board[x][y].alive as u8,
+            None => border as u8,
+        };
+        let (width, height) = (self.width as i64, self.height as i64);
+        // columns[x + 1] is the sum of column x from the row above to the
+        // row below, counting from 0, including the columns past each edge
+        let mut columns = vec![0u8; self.width + 2];
+        (0..height)
+            .map(|y| {
+                for x in -1..=width {
+                    columns[(x + 1) as usize] =
+                        if (0..width).contains(&x) && (1..height - 1).contains(&y) {
+                            let column = &self.board[x as usize + 1][y as usize..=y as usize + 2];
+                            column.iter().map(|cell| cell.alive as u8).sum()
+                        } else {
+                            alive(x, y - 1) + alive(x, y) + alive(x, y + 1)
+                        };
+                }
+                let mut sum = columns[0] + columns[1];
+                (0..self.width)
+                    .map(|x| {
+                        sum += columns[x + 2];
+                        let count = sum - self.board[x + 1][y as usize + 1].alive as u8;
+                        sum -= columns[x];
+                        count
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn get_cell_i(&self, x: isize, y: isize) -> Option<&Cell> {
+        // The cell at (x, y) counting from 0, so (0, 0) is the top-left
+        // cell, get_cell((1, 1)). Coordinates off the board are None,
+        // except on a wrapped board, where they wrap round as far as
+        // they go.
+        self.signed_coords(Coord::new(x as i64, y as i64))
+            .map(|(x, y)| &self.board[x][y])
+    }
+
+    pub fn get_cell_i_mut(&mut self, x: isize, y: isize) -> Option<&mut Cell> {
+        // The same as get_cell_i, forgetting the population and hash
+        // like get_cell_mut does
+        let coords = self.signed_coords(Coord::new(x as i64, y as i64))?;
+        self.get_cell_mut(coords)
+    }
+
+    pub fn index_of(&self, coord: Coord) -> Option<usize> {
+        // Where the cell at `coord` comes in iter's row-by-row order,
+        // or None if it's off the board. Never wraps.
+        let (width, height) = (self.width as i64, self.height as i64);
+        if (0..width).contains(&coord.x) && (0..height).contains(&coord.y) {
+            Some((coord.y * width + coord.x) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn coord_of(&self, index: usize) -> Option<Coord> {
+        // The cell at `index` in iter's order, the reverse of index_of
+        if index < self.width * self.height {
+            Some(Coord::new(
+                (index % self.width) as i64,
+                (index / self.width) as i64,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn signed_coords(&self, coord: Coord) -> Option<(usize, usize)> {
+        // Board coordinates of the cell at `coord`, wrapping round on a
+        // wrapped board
+        let coord = if self.wrap {
+            Coord::new(
+                coord.x.rem_euclid(self.width as i64),
+                coord.y.rem_euclid(self.height as i64),
+            )
+        } else {
+            coord
+        };
+        self.index_of(coord)
+            .map(|_| (coord.x as usize + 1, coord.y as usize + 1))
+    }
+
+    fn onto_board(&self, coord: Coord) -> Option<(usize, usize)> {
+        // Board coordinates of the cell at `coord`, or for a cell just
+        // past an edge, of the cell on the board that stands in for it,
+        // if there is one
+        if let Some(coords) = self.signed_coords(coord) {
+            return Some(coords);
+        }
+        if self.wrap || self.border_mode != BorderMode::Reflective {
+            return None;
+        }
+        let reflect = |c: i64, size: usize| match c {
+            -1 => Some(0),
+            c if c == size as i64 => Some(c - 1),
+            c => Some(c).filter(|&c| (0..size as i64).contains(&c)),
+        };
+        self.signed_coords(Coord::new(
+            reflect(coord.x, self.width)?,
+            reflect(coord.y, self.height)?,
+        ))
+    }
+
+    pub fn get_cells_to_flip(&self) -> Vec<(usize, usize)> {
+        // Identifies the cells that need to be flipped, only looking
+        // at cells that are alive or next to an alive cell
+
+        let mut candidates = vec![vec![false; self.height + 1]; self.width + 1];
+        self.mark_candidates(&mut candidates);
+        self.cells_to_flip(Some(&candidates))
+    }
+
+    fn cells_to_flip(&self, candidates: Option<&[Vec<bool>]>) -> Vec<(usize, usize)> {
+        // Iterates across the board, identifying cells that need to be
+        // flipped. Cells not marked in `candidates` flip only if the
+        // background does, or every cell is checked if there are no
+        // candidates.
+
+        let mut cells_to_flip: Vec<(usize, usize)> = Vec::new();
+        let background_flips = self.next_background() != self.background;
+
+        for ((x, y), alive) in self.iter() {
+            if let Some(candidates) = candidates {
+                if !candidates[x][y] {
+                    if background_flips {
+                        cells_to_flip.push((x, y));
+                    }
+                    continue;
+                }
+            }
+            let num_adjacent_alive = self.count_adjacent_alive((x, y));
+            if self.next_state(alive, num_adjacent_alive) != alive {
+                cells_to_flip.push((x, y));
+            }
+        }
+        cells_to_flip
+    }
+
+    pub fn tick(&mut self) -> TickStats {
+        // Advances a generation, returning what changed. The numbers are
+        // counted while the next generation is written, so they cost
+        // nothing extra.
+        self.record_history();
+        let population_before = self.population();
+        self.advance(self.generation + 1);
+        self.generation += 1;
+        self.run_hooks();
+        TickStats {
+            generation: self.generation,
+            births: self.births,
+            deaths: self.deaths,
+            population_before,
+            population_after: self.population(),
+        }
+    }
+
+    pub fn tick_diff(&mut self) -> Vec<(usize, usize, bool)> {
+        // Ticks, returning the board coordinates of every cell that
+        // changed and whether it's now alive, row by row from the
+        // top-left, so a view can redraw only those. If the board grew,
+        // the coordinates are on the grown board. After the swap in
+        // advance the next buffer holds the generation before, so the
+        // two are compared without copying anything.
+        self.tick();
+        (1..=self.height)
+            .flat_map(|y| (1..=self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.board[x][y].alive != self.next[x][y].alive)
+            .map(|(x, y)| (x, y, self.board[x][y].alive))
+            .collect()
+    }
+
+    pub fn tick_n(&mut self, n: u64) -> u64 {
+        // Ticks `n` times, keeping only the board from before the first
+        // tick in the history, so a single undo goes back over all of
+        // them. The two buffers are swapped each time, as in tick.
+        //
+        // Once the board settles into a still life or an oscillator, the
+        // generations left that would only go round the cycle again are
+        // skipped, so the board still ends up as it would be after `n`
+        // ticks. Returns how many generations were actually computed.
+        // Nothing is skipped while activity, edge events or hooks are
+        // being tracked, since they need to see every generation, nor on
+        // a board that can grow, since growing shifts every cell and a
+        // spaceship could land back where it started.
+        if n == 0 {
+            return 0;
+        }
+        self.record_history();
+        let skippable = self.activity.is_none()
+            && self.edge_events.is_none()
+            && self.hooks.is_empty()
+            && (self.max_size.is_none() || self.wrap);
+        let end = self.generation + n;
+        let mut detector = CycleDetector::default();
+        let mut computed = 0;
+        let mut left = n;
+        while left > 0 {
+            if skippable {
+                if let Some(cycle) = detector.record(computed, self.cycle_hash()) {
+                    left %= cycle.period;
+                    if left == 0 {
+                        break;
+                    }
+                    detector.clear();
+                }
+            }
+            computed += 1;
+            left -= 1;
+            self.advance(self.generation + 1);
+            self.generation += 1;
+            self.run_hooks();
+        }
+        self.generation = end;
+        computed
+    }
+
+    fn cycle_hash(&self) -> u64 {
+        // The Zobrist hash, told apart by the background as well, since
+        // under B0 rules the same cells can be alive on either
+        if self.background {
+            !self.zobrist_hash()
+        } else {
+            self.zobrist_hash()
+        }
+    }
+
+    fn record_history(&mut self) {
+        if self.history.max_depth() > 0 {
+            let snapshot = self.snapshot();
+            self.history.record(snapshot);
+        }
+    }
+
+    fn advance(&mut self, generation: u64) {
+        // Moves the cells on to `generation`, growing the board first if
+        // it's allowed to. The generation counter is left to the caller.
+        if self.max_size.is_some() && !self.wrap {
+            self.grow();
+        }
+        self.detect_edge_crossings(generation);
+
+        // Every cell of the next buffer is overwritten, so whatever it
+        // held from two generations ago doesn't matter. Cells that
+        // aren't candidates are surrounded by the background and match
+        // it, so they take its next state without being looked at.
+        let hash = self.zobrist_hash();
+        let population = self.population();
+        let mut candidates = std::mem::take(&mut self.candidates);
+        let mut next = std::mem::take(&mut self.next);
+        self.mark_candidates(&mut candidates);
+        let changes = match self.parallelism {
+            Parallelism::Serial => next.iter_mut().enumerate().skip(1).fold(
+                Changes::default(),
+                |changes, (x, column)| {
+                    changes.merge(self.write_next_column(x, &candidates[x], column))
+                },
+            ),
+            Parallelism::Strips(threads) => {
+                let threads = threads.unwrap_or_else(|| {
+                    std::thread::available_parallelism().map_or(1, |threads| threads.get())
+                });
+                self.write_next_strips(threads, &candidates, &mut next)
+            }
+            #[cfg(feature = "parallel")]
+            Parallelism::Rayon => self.write_next_rayon(&candidates, &mut next),
+        };
+        self.zobrist = Some(hash ^ changes.hash);
+        self.population = Some(population + changes.births - changes.deaths);
+        self.births = changes.births;
+        self.deaths = changes.deaths;
+        self.background = self.next_background();
+        self.candidates = candidates;
+        self.next = next;
+        std::mem::swap(&mut self.board, &mut self.next);
+
+        if let Some(activity) = &mut self.activity {
+            for (x, column) in self.board.iter().enumerate().skip(1) {
+                for (y, cell) in column.iter().enumerate().skip(1) {
+                    if cell.alive {
+                        activity[(y - 1) * self.width + (x - 1)] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_next_strips(
+        &self,
+        threads: usize,
+        candidates: &[Vec<bool>],
+        next: &mut [Vec<Cell>],
+    ) -> Changes {
+        // Same as the serial loop in tick, with the columns split into
+        // `threads` strips of neighbouring columns, each written by its
+        // own thread. Cells on the edge of a strip read the columns on
+        // either side of it from the current generation, which is never
+        // written during the tick, so the strips don't need to share.
+
+        let columns = &mut next[1..];
+        let strip_width = columns.len().div_ceil(threads.max(1)).max(1);
+        std::thread::scope(|scope| {
+            let strips: Vec<_> = columns
+                .chunks_mut(strip_width)
+                .enumerate()
+                .map(|(i, strip)| {
+                    let first = 1 + i * strip_width;
+                    scope.spawn(move || {
+                        strip.iter_mut().enumerate().fold(
+                            Changes::default(),
+                            |changes, (j, column)| {
+                                let x = first + j;
+                                changes.merge(self.write_next_column(x, &candidates[x], column))
+                            },
+                        )
+                    })
+                })
+                .collect();
+            strips
+                .into_iter()
+                .fold(Changes::default(), |changes, strip| {
+                    changes.merge(strip.join().unwrap())
+                })
+        })
+    }
+
+    #[cfg(feature = "parallel")]
+    fn write_next_rayon(&self, candidates: &[Vec<bool>], next: &mut [Vec<Cell>]) -> Changes {
+        // Same as the serial loop in tick, with the columns shared out
+        // between threads. Each column is only written by one thread,
+        // and the current generation is only read.
+        use rayon::prelude::*;
+
+        next.par_iter_mut()
+            .enumerate()
+            .skip(1)
+            .map(|(x, column)| self.write_next_column(x, &candidates[x], column))
+            .reduce(Changes::default, Changes::merge)
+    }
+
+    fn write_next_column(&self, x: usize, candidates: &[bool], column: &mut [Cell]) -> Changes {
+        // Writes column `x` of the next generation into `column`,
+        // returning what changed
+        let mut changes = Changes::default();
+        let background = self.next_background();
+        for (y, (cell, &candidate)) in column.iter_mut().zip(candidates).enumerate().skip(1) {
+            if !candidate {
+                cell.alive = background;
+                if background != self.background {
+                    changes.record((x, y), background);
+                }
+                continue;
+            }
+            let alive = self.board[x][y].alive;
+            cell.alive = self.next_state(alive, self.count_adjacent_alive((x, y)));
+            if cell.alive != alive {
+                changes.record((x, y), cell.alive);
+            }
+        }
+        changes
+    }
+
+    pub fn run_collecting_stats(&mut self, gens: u64) -> Vec<usize> {
+        // Ticks `gens` times, returning the population after each tick
+        (0..gens)
+            .map(|_| {
+                self.tick();
+                self.population()
+            })
+            .collect()
+    }
+
+    fn mark_candidates(&self, candidates: &mut [Vec<bool>]) {
+        // Marks every cell that differs from the background or is next
+        // to one that does. Any other cell is surrounded by background,
+        // so does whatever the background does in the next generation.
+        // Rebuilt from scratch each time because cells can be
+        // edited through get_cell_mut between ticks.
+
+        for column in candidates.iter_mut() {
+            for candidate in column.iter_mut() {
+                *candidate = false;
+            }
+        }
+        if !self.wrap && self.border_mode != BorderMode::Dead {
+            // Cells along the edges see the border, which needn't match
+            // the background
+            for (x, column) in candidates.iter_mut().enumerate().skip(1) {
+                for (y, candidate) in column.iter_mut().enumerate().skip(1) {
+                    if x == 1 || y == 1 || x == self.width || y == self.height {
+                        *candidate = true;
+                    }
+                }
+            }
+        }
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                if self.board[x][y].alive == self.background {
+                    continue;
+                }
+                if self.wrap {
+                    let coord = signed((x, y));
+                    for dx in -1..=1 {
+                        for dy in -1..=1 {
+                            if let Some((nx, ny)) = self.signed_coords(coord.offset(dx, dy)) {
+                                candidates[nx][ny] = true;
+                            }
+                        }
+                    }
+                    continue;
+                }
+                for column in &mut candidates[x - 1..=(x + 1).min(self.width)] {
+                    for candidate in &mut column[y - 1..=(y + 1).min(self.height)] {
+                        *candidate = true;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        // Kills every cell and resets the generation counter,
+        // reusing the board's existing allocation.
+
+        for column in self.board.iter_mut() {
+            for cell in column.iter_mut() {
+                cell.alive = false;
+            }
+        }
+        self.zobrist = Some(0);
+        self.population = Some(0);
+        self.background = false;
+        self.births = 0;
+        self.deaths = 0;
+        self.generation = 0;
+    }
+
+    pub fn fill(&mut self) {
+        // Makes every cell on the board alive, keeping the generation.
+        // Cells beyond the edges aren't touched.
+        self.for_each_mut(|_, _| true);
+    }
+
+    pub fn invert(&mut self) {
+        // Makes every alive cell on the board dead and every dead one
+        // alive. Cells beyond the edges aren't touched.
+        self.for_each_mut(|_, alive| !alive);
+    }
+
+    pub fn reset_random(&mut self, seed: u64, density: f64) {
+        // Clears the board, then makes each cell alive with probability
+        // `density`. The same seed always gives the same board.
+
+        self.clear();
+        let (width, height) = (self.width as i64, self.height as i64);
+        let origin = self.stable_origin();
+        randomize(
+            self,
+            origin,
+            width,
+            height,
+            density,
+            &mut StdRng::seed_from_u64(seed),
+        );
+    }
+
+    pub fn copy_region(&self, x1: usize, y1: usize, x2: usize, y2: usize) -> Board {
+        // Copies the rectangle with corners (x1, y1) and (x2, y2)
+        // inclusive, in either order, into a new board of that size,
+        // whose top-left cell is (1, 1). Parts of the rectangle beyond
+        // the board are copied as dead cells.
+
+        let (x1, x2) = (x1.min(x2), x1.max(x2));
+        let (y1, y2) = (y1.min(y2), y1.max(y2));
+        let mut region = Board::with_size(x2 - x1 + 1, y2 - y1 + 1);
+        region.glyphs = self.glyphs;
+        for x in x1..=x2 {
+            for y in y1..=y2 {
+                if let Some(cell) = self.get_cell((x, y)) {
+                    region.set_alive_unchecked((x - x1 + 1, y - y1 + 1), cell.alive);
+                }
+            }
+        }
+        region
+    }
+
+    pub fn paste_region(&mut self, src: &Board, origin: (usize, usize)) {
+        // Places `src` onto this board with its top-left cell at `origin`.
+        // Alive cells in `src` are made alive, but cells it leaves dead
+        // keep their current state. Anything that lands beyond the
+        // board is dropped.
+
+        for x in 1..=src.width {
+            for y in 1..=src.height {
+                if !src.board[x][y].alive {
+                    continue;
+                }
+                let coords = (origin.0 + x - 1, origin.1 + y - 1);
+                if self.get_cell(coords).is_some() {
+                    self.set_alive_unchecked(coords, true);
+                }
+            }
+        }
+    }
+
+    pub fn find_islands(&self) -> Vec<Vec<(usize, usize)>> {
+        // Groups the alive cells into islands of cells that touch,
+        // including diagonally. Each island's cells are listed row by
+        // row from the top-left, and the islands are ordered by their
+        // first cell in the same way.
+
+        let mut seen = vec![vec![false; self.height + 1]; self.width + 1];
+        let mut islands = Vec::new();
+
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                if !self.board[x][y].alive || seen[x][y] {
+                    continue;
+                }
+                let mut island = self.island_from((x, y), &mut seen);
+                island.sort_by_key(|&(x, y)| (y, x));
+                islands.push(island);
+            }
+        }
+        islands.sort_by_key(|island| (island[0].1, island[0].0));
+        islands
+    }
+
+    fn mirror(&self, axis: SymmetryAxis, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        // The cell `axis` maps (x, y) onto, or None if it's in the half
+        // that symmetrize copies from. On a board that isn't square, only
+        // the square in the top-left corner has a diagonal, so cells
+        // outside it are left out as well.
+        let (width, height) = (self.width, self.height);
+        match axis {
+            SymmetryAxis::Horizontal => (2 * x > width + 1).then(|| (width + 1 - x, y)),
+            SymmetryAxis::Vertical => (2 * y > height + 1).then(|| (x, height + 1 - y)),
+            SymmetryAxis::Diagonal => {
+                let side = width.min(height);
+                (x > y && x <= side).then_some((y, x))
+            }
+            SymmetryAxis::Rotate180 => {
+                let opposite = (width + 1 - x, height + 1 - y);
+                ((y, x) > (opposite.1, opposite.0)).then_some(opposite)
+            }
+        }
+    }
+
+    pub fn symmetrize(&mut self, axis: SymmetryAxis) {
+        // Copies one half of the board onto the other, mirrored about
+        // `axis`: the left half onto the right, the top half onto the
+        // bottom, the part below the diagonal onto the part above, or
+        // the top half onto the bottom turned half a turn. The cells
+        // copied from are never written, so the board is read as it goes.
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                if let Some((mx, my)) = self.mirror(axis, (x, y)) {
+                    let alive = self.board[mx][my].alive;
+                    self.set_alive_unchecked((x, y), alive);
+                }
+            }
+        }
+    }
+
+    pub fn is_symmetric(&self, axis: SymmetryAxis) -> bool {
+        // Whether symmetrize(axis) would leave the board as it is
+        self.iter()
+            .all(|(coords, alive)| match self.mirror(axis, coords) {
+                Some((mx, my)) => self.board[mx][my].alive == alive,
+                None => true,
+            })
+    }
+
+    pub fn is_horizontally_symmetric(&self) -> bool {
+        // Whether the board is its own mirror image left to right
+        self.is_symmetric(SymmetryAxis::Horizontal)
+    }
+
+    pub fn count_clusters(&self) -> usize {
+        // Number of separate objects on the board: the islands
+        // find_islands would give, counted without listing their cells
+        let mut seen = vec![vec![false; self.height + 1]; self.width + 1];
+        let mut clusters = 0;
+        for ((x, y), alive) in self.iter() {
+            if alive && !seen[x][y] {
+                self.island_from((x, y), &mut seen);
+                clusters += 1;
+            }
+        }
+        clusters
+    }
+
+    fn island_from(&self, origin: (usize, usize), seen: &mut [Vec<bool>]) -> Vec<(usize, usize)> {
+        // Breadth-first flood fill from `origin` through the alive cells
+        // not yet marked in `seen`, marking the ones it reaches. `origin`
+        // is always included, alive or not.
+        let mut island = Vec::new();
+        let mut queue = VecDeque::new();
+        seen[origin.0][origin.1] = true;
+        queue.push_back(origin);
+        while let Some((x, y)) = queue.pop_front() {
+            island.push((x, y));
+            for (nx, ny) in self.surrounding_cells((x, y)) {
+                if self.board[nx][ny].alive && !seen[nx][ny] {
+                    seen[nx][ny] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        island
+    }
+
+    fn surrounding_cells(
+        &self,
+        coords: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // The eight cells around `coords` that are on the board
+        let (x, y) = coords;
+        (0..3)
+            .flat_map(|dx| (0..3).map(move |dy| (dx, dy)))
+            .filter(|&(dx, dy)| (dx, dy) != (1, 1))
+            // get_cell is None off the board, including at x or y of 0
+            .map(move |(dx, dy)| (x + dx - 1, y + dy - 1))
+            .filter(move |&coords| self.get_cell(coords).is_some())
+    }
+
+    pub fn flood_fill_alive(&mut self, origin: (usize, usize)) -> Result<usize, BoardError> {
+        // Makes `origin` alive, then every dead cell touching the island
+        // of alive cells it's part of, including diagonally, so the
+        // island grows by a ring of cells. Returns how many cells came
+        // to life, or an error if `origin` isn't on the board.
+        let was_alive = self.get_cell(origin).map(|cell| cell.alive);
+        self.set_alive(origin, true)?;
+        let mut seen = vec![vec![false; self.height + 1]; self.width + 1];
+        let island = self.island_from(origin, &mut seen);
+        let mut changed = usize::from(was_alive == Some(false));
+        for coords in island {
+            let ring: Vec<_> = self.surrounding_cells(coords).collect();
+            for (x, y) in ring {
+                if !self.board[x][y].alive {
+                    self.set_alive_unchecked((x, y), true);
+                    changed += 1;
+                }
+            }
+        }
+        Ok(changed)
+    }
+
+    pub fn flood_fill_dead(&mut self, origin: (usize, usize)) -> Result<usize, BoardError> {
+        // Kills the island of alive cells that `origin` is part of,
+        // counting cells that touch diagonally. Returns how many cells
+        // died, which is 0 if `origin` is dead, or an error if it isn't
+        // on the board.
+        match self.get_cell(origin) {
+            None => Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords: origin,
+                board: (self.width, self.height),
+            })),
+            Some(cell) if !cell.alive => Ok(0),
+            Some(_) => {
+                let mut seen = vec![vec![false; self.height + 1]; self.width + 1];
+                let island = self.island_from(origin, &mut seen);
+                for &coords in &island {
+                    self.set_alive_unchecked(coords, false);
+                }
+                Ok(island.len())
+            }
+        }
+    }
+
+    pub fn center_pattern(&mut self) -> Result<(), BoardError> {
+        // Moves every alive cell by the same amount so that the bounding
+        // box of the pattern sits in the middle of the board. When it
+        // can't be exactly centred, the extra dead cell goes on the
+        // right or bottom. Does nothing to an empty board.
+
+        let alive: Vec<(usize, usize)> = (1..=self.width)
+            .flat_map(|x| (1..=self.height).map(move |y| (x, y)))
+            .filter(|&(x, y)| self.board[x][y].alive)
+            .collect();
+        if alive.is_empty() {
+            return Ok(());
+        }
+        let min_x = alive.iter().map(|&(x, _)| x).min().unwrap();
+        let max_x = alive.iter().map(|&(x, _)| x).max().unwrap();
+        let min_y = alive.iter().map(|&(_, y)| y).min().unwrap();
+        let max_y = alive.iter().map(|&(_, y)| y).max().unwrap();
+
+        // Every alive cell is on the board, so this only fails if that
+        // ever stops being true. Refuse rather than clip the pattern.
+        let pattern = (max_x - min_x + 1, max_y - min_y + 1);
+        if pattern.0 > self.width || pattern.1 > self.height {
+            return Err(BoardError::PatternTooLarge(PatternTooLargeError {
+                pattern,
+                board: (self.width, self.height),
+            }));
+        }
+
+        // Top-left corner of the bounding box once centred
+        let x = (self.width - pattern.0) / 2 + 1;
+        let y = (self.height - pattern.1) / 2 + 1;
+        for &(old_x, old_y) in &alive {
+            self.set_alive_unchecked((old_x, old_y), false);
+        }
+        for &(old_x, old_y) in &alive {
+            self.set_alive_unchecked((old_x - min_x + x, old_y - min_y + y), true);
+        }
+        Ok(())
+    }
+
+    pub fn center_of_mass(&self) -> Option<(f64, f64)> {
+        // Mean position of the alive cells, or None if there are none.
+        // Uses stable (Life trait) coordinates, which are the same as
+        // board coordinates unless the board has grown.
+        let origin = self.stable_origin();
+        life::center_of_mass(self, origin, self.width as i64, self.height as i64)
+    }
+
+    pub fn center_of_mass_velocity(&self, prev: &Board) -> (f64, f64) {
+        // How far the center of mass has moved since `prev`. Zero if
+        // either board is empty, since there's nothing to follow.
+        match (self.center_of_mass(), prev.center_of_mass()) {
+            (Some(now), Some(before)) => (now.0 - before.0, now.1 - before.1),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    pub fn snapshot(&self) -> BoardSnapshot {
+        // Packs the live state and generation into one bit per cell, to
+        // return to later with restore. Settings such as the rule aren't
+        // part of it.
+        let mut cells = vec![0; (self.width * self.height).div_ceil(64)];
+        for (i, alive) in self.alive_states().enumerate() {
+            if alive {
+                cells[i / 64] |= 1 << (i % 64);
+            }
+        }
+        BoardSnapshot {
+            width: self.width,
+            height: self.height,
+            generation: self.generation,
+            offset: self.offset,
+            background: self.background,
+            cells,
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &BoardSnapshot) {
+        // Puts the board back into the state captured by `snapshot`,
+        // resizing it if it has grown since. The undo history is left
+        // alone, so a snapshot can be restored any number of times.
+
+        if (self.width, self.height) != (snapshot.width, snapshot.height) {
+            let mut resized = self.resized(snapshot.width, snapshot.height);
+            resized.offset = snapshot.offset;
+            resized.carry_activity(self);
+            *self = resized;
+        }
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                let i = (x - 1) * self.height + (y - 1);
+                self.board[x][y].alive = snapshot.cells[i / 64] >> (i % 64) & 1 == 1;
+            }
+        }
+        self.zobrist = Some(self.compute_zobrist());
+        self.population = Some(self.count_population());
+        self.generation = snapshot.generation;
+        self.offset = snapshot.offset;
+        self.background = snapshot.background;
+        // Nothing is kept about how the snapshot's generation came about
+        self.births = 0;
+        self.deaths = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), bool)> + '_ {
+        // Board coordinates of every cell and whether it's alive, row by
+        // row from the top-left. Every coordinate is accepted by get_cell.
+        (1..=self.height)
+            .flat_map(move |y| (1..=self.width).map(move |x| ((x, y), self.board[x][y].alive)))
+    }
+
+    pub fn for_each_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut((usize, usize), bool) -> bool,
+    {
+        // Calls `f` with the coordinates and state of every cell, in the
+        // same order as iter, and makes the cell alive if it returns true
+        // or dead if it returns false
+        for y in 1..=self.height {
+            for x in 1..=self.width {
+                let alive = f((x, y), self.board[x][y].alive);
+                self.set_alive_unchecked((x, y), alive);
+            }
+        }
+    }
+
+    pub fn diff(&self, other: &Board) -> Result<BoardDiff, BoardError> {
+        // The cells that come to life and die in going from this board
+        // to `other`. Only the cells on the boards are compared, so the
+        // generation, rule and background don't matter.
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(BoardError::DimensionMismatch(SizeMismatchError {
+                left: (self.width, self.height),
+                right: (other.width, other.height),
+            }));
+        }
+        let mut diff = BoardDiff::default();
+        for ((coords, before), (_, after)) in self.iter().zip(other.iter()) {
+            if after && !before {
+                diff.births.push(coords);
+            } else if before && !after {
+                diff.deaths.push(coords);
+            }
+        }
+        Ok(diff)
+    }
+
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // Board coordinates of the alive cells, in the same order as iter
+        self.iter()
+            .filter(|&(_, alive)| alive)
+            .map(|(coords, _)| coords)
+    }
+
+    fn alive_states(&self) -> impl Iterator<Item = bool> + '_ {
+        // Whether each cell is alive, column by column
+        self.board[1..=self.width]
+            .iter()
+            .flat_map(move |column| column[1..=self.height].iter().map(|cell| cell.alive))
+    }
+
+    fn stable_origin(&self) -> (i64, i64) {
+        // Stable (Life trait) coordinates of the top-left cell
+        (self.offset.0 + 1, self.offset.1 + 1)
+    }
+
+    fn board_coords(&self, coords: (i64, i64)) -> Option<(usize, usize)> {
+        // Converts stable (Life trait) coordinates into board coordinates,
+        // or None if they fall to the left of or above the board.
+        let x = coords.0 - self.offset.0;
+        let y = coords.1 - self.offset.1;
+        if x < 1 || y < 1 {
+            None
+        } else {
+            Some((x as usize, y as usize))
+        }
+    }
+
+    fn grow(&mut self) {
+        // Reallocates the board with GROW_MARGIN more cells on every side
+        // that has a cell other than the background within 2 cells of it,
+        // without exceeding max_size. Cells keep their stable (Life trait)
+        // coordinates, and the new ones take the background's state.
+
+        let max_size = match self.max_size {
+            Some(max_size) => max_size,
+            None => return,
+        };
+
+        let (mut left, mut right, mut top, mut bottom) = (false, false, false, false);
+        for (x, column) in self.board.iter().enumerate().take(self.width + 1).skip(1) {
+            for (y, _) in column
+                .iter()
+                .enumerate()
+                .take(self.height + 1)
+                .skip(1)
+                .filter(|(_, cell)| cell.alive != self.background)
+            {
+                left |= x <= 2;
+                right |= x + 2 > self.width;
+                top |= y <= 2;
+                bottom |= y + 2 > self.height;
+            }
+        }
+
+        let spare_width = max_size.0.saturating_sub(self.width);
+        let spare_height = max_size.1.saturating_sub(self.height);
+        let grow_left = if left {
+            GROW_MARGIN.min(spare_width)
+        } else {
+            0
+        };
+        let grow_right = if right {
+            GROW_MARGIN.min(spare_width - grow_left)
+        } else {
+            0
+        };
+        let grow_top = if top {
+            GROW_MARGIN.min(spare_height)
+        } else {
+            0
+        };
+        let grow_bottom = if bottom {
+            GROW_MARGIN.min(spare_height - grow_top)
+        } else {
+            0
+        };
+        if grow_left + grow_right + grow_top + grow_bottom == 0 {
+            return;
+        }
+
+        let mut grown = self.resized(
+            self.width + grow_left + grow_right,
+            self.height + grow_top + grow_bottom,
+        );
+        if self.background {
+            for column in &mut grown.board[1..] {
+                for cell in &mut column[1..] {
+                    cell.alive = true;
+                }
+            }
+        }
+        for x in 1..=self.width {
+            for y in 1..=self.height {
+                grown.board[x + grow_left][y + grow_top].alive = self.board[x][y].alive;
+            }
+        }
+        // Every cell has moved, so the hash has to start again
+        grown.zobrist = Some(grown.compute_zobrist());
+        grown.population = Some(grown.count_population());
+        grown.offset = (
+            self.offset.0 - grow_left as i64,
+            self.offset.1 - grow_top as i64,
+        );
+        grown.generation = self.generation;
+        grown.carry_activity(self);
+        *self = grown;
+    }
+
+    fn resized(&mut self, width: usize, height: usize) -> Board {
+        // An empty board of the given size that takes over this
+        // board's settings and history
+        let mut resized = Board::with_size(width, height);
+        resized.max_size = self.max_size;
+        resized.history = std::mem::take(&mut self.history);
+        resized.parallelism = self.parallelism;
+        resized.rule = self.rule;
+        resized.transition = self.transition.clone();
+        resized.wrap = self.wrap;
+        resized.neighborhood = self.neighborhood;
+        resized.border_mode = self.border_mode;
+        resized.background = self.background;
+        resized.glyphs = self.glyphs;
+        resized.activity = self.activity.as_ref().map(|_| vec![0; width * height]);
+        resized.edge_events = self.edge_events.take();
+        resized.hooks = std::mem::take(&mut self.hooks);
+        resized
+    }
+
+    fn render_pixels(&self, origin: (i64, i64), width: usize, height: usize) -> Vec<u8> {
+        // Rasterizes the `width` x `height` cells starting at the stable
+        // coordinates `origin` into one palette index per pixel, 1 for
+        // alive and 0 for dead, with each cell drawn as a
+        // PIXEL_SCALE x PIXEL_SCALE square.
+
+        let pixel_width = width * PIXEL_SCALE;
+        let mut pixels = vec![0; pixel_width * height * PIXEL_SCALE];
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            let x = origin.0 + (i % pixel_width / PIXEL_SCALE) as i64;
+            let y = origin.1 + (i / pixel_width / PIXEL_SCALE) as i64;
+            *pixel = self.is_alive_at((x, y)) as u8;
+        }
+        pixels
+    }
+
+    pub fn record_gif(&mut self, generations: usize, delay_cs: u16, path: &Path) -> io::Result<()> {
+        // Writes `generations` frames to an endlessly looping GIF at `path`,
+        // ticking the board after each frame. `delay_cs` is the time each
+        // frame is shown for, in centiseconds (hundredths of a second).
+        // Every frame shows the area the board covers when recording
+        // starts, even if it grows along the way.
+
+        // Palette index 0 is dead (white), index 1 is alive (black)
+        let palette = [0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00];
+        let too_large = |_| io::Error::other("board is too large for a GIF");
+        let width = u16::try_from(self.width * PIXEL_SCALE).map_err(too_large)?;
+        let height = u16::try_from(self.height * PIXEL_SCALE).map_err(too_large)?;
+        let (origin, cells_wide, cells_high) = (self.stable_origin(), self.width, self.height);
+
+        let file = File::create(path)?;
+        let mut encoder =
+            gif::Encoder::new(file, width, height, &palette).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for _ in 0..generations {
+            let mut frame = gif::Frame::from_indexed_pixels(
+                width,
+                height,
+                self.render_pixels(origin, cells_wide, cells_high),
+                None,
+            );
+            frame.delay = delay_cs;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+            self.tick();
+        }
+        Ok(())
+    }
+
+    pub fn render_heatmap(&self, heat: &HeatMap) -> String {
+        // Draws the board like Display does, but shading each cell by
+        // how often it has been alive, from cold ░ to hot █
+        let mut output = String::new();
+        for y in 1..=self.height {
+            for x in 1..=self.width {
+                output.push(heat.glyph((x, y)));
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn render_scaled(&self, max_width: usize, max_height: usize) -> String {
+        // Draws the board like Display does, shrunk to at most
+        // `max_width` by `max_height` characters for boards too big for
+        // the terminal. Each character stands for a square block of
+        // cells, the same size across and down so shapes keep their
+        // proportions, and is alive if any cell in the block is. A
+        // board that already fits is drawn a cell per character.
+        if max_width == 0 || max_height == 0 {
+            return String::new();
+        }
+        let block = self
+            .width
+            .div_ceil(max_width)
+            .max(self.height.div_ceil(max_height))
+            .max(1);
+        let (alive, dead) = self.glyphs;
+        let mut output = String::new();
+        for top in (1..=self.height).step_by(block) {
+            for left in (1..=self.width).step_by(block) {
+                let bottom = (top + block - 1).min(self.height);
+                let right = (left + block - 1).min(self.width);
+                let any_alive = (left..=right)
+                    .any(|x| self.board[x][top..=bottom].iter().any(|cell| cell.alive));
+                output.push(if any_alive { alive } else { dead });
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn render_braille(&self) -> String {
+        // Draws the board with a braille character for each block of
+        // 2x4 cells, raising a dot for each alive cell. Blocks at the
+        // right and bottom edges can run past the board, and the cells
+        // beyond it are left blank.
+        let mut output = String::new();
+        for top in (1..=self.height).step_by(4) {
+            for left in (1..=self.width).step_by(2) {
+                let mut codepoint = 0x2800;
+                for (dy, row) in BRAILLE_DOTS.iter().enumerate() {
+                    for (dx, dot) in row.iter().enumerate() {
+                        let alive = self
+                            .get_cell((left + dx, top + dy))
+                            .is_some_and(|cell| cell.alive);
+                        if alive {
+                            codepoint |= dot;
+                        }
+                    }
+                }
+                output.push(char::from_u32(codepoint).unwrap());
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    pub fn from_life106(input: &str) -> Result<Board, GolError> {
+        // Reads a pattern in Life 1.06 format: a `#Life 1.06` header,
+        // then the x and y coordinates of one alive cell per line.
+        // The board is just big enough to hold the pattern with
+        // PATTERN_MARGIN dead cells around it, and its stable (Life
+        // trait) coordinates are the ones in the file. Cells too far
+        // apart for a board of MAX_LOADED_SIZE are InvalidSize, and
+        // ones too near the ends of i64 to leave room for the margin
+        // are a Parse error.
+
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()));
+        match lines.find(|(_, line)| !line.is_empty()) {
+            Some((_, LIFE106_HEADER)) => (),
+            other => {
+                // An empty input is reported at line 1
+                return Err(GolError::Parse {
+                    line: other.map_or(1, |(line, _)| line),
+                    message: format!("expected a {} header", LIFE106_HEADER),
+                });
+            }
+        }
+
+        let mut cells = Vec::new();
+        for (line, text) in lines {
+            // Golly writes further `#` lines as comments
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let mut parts = text.split_whitespace().map(str::parse::<i64>);
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => {
+                    let reach = PATTERN_MARGIN as i64 + 1;
+                    if [x, y]
+                        .iter()
+                        .any(|&n| n.checked_sub(reach).is_none() || n.checked_add(reach).is_none())
+                    {
+                        return Err(GolError::Parse {
+                            line,
+                            message: format!("coordinates {:?} are too far out", text),
+                        });
+                    }
+                    cells.push((x, y))
+                }
+                _ => {
+                    return Err(GolError::Parse {
+                        line,
+                        message: format!("expected a pair of coordinates, found {:?}", text),
+                    })
+                }
+            }
+        }
+
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let max_x = cells.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_y = cells.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+        // A span too wide for a usize is as good as usize::MAX, which
+        // check_loaded_size turns down all the same
+        let side = |min: i64, max: i64| {
+            max.checked_sub(min)
+                .and_then(|span| span.checked_add(1))
+                .and_then(|span| usize::try_from(span).ok())
+                .and_then(|span| span.checked_add(2 * PATTERN_MARGIN))
+                .unwrap_or(usize::MAX)
+        };
+        let (width, height) = (side(min_x, max_x), side(min_y, max_y));
+        check_loaded_size(width, height)?;
+        let mut board = Board::with_size(width, height);
+        // Puts the leftmost and topmost cells just inside the margin
+        board.offset = (
+            min_x - PATTERN_MARGIN as i64 - 1,
+            min_y - PATTERN_MARGIN as i64 - 1,
+        );
+        for coords in cells {
+            board.set_alive_at(coords, true);
+        }
+        Ok(board)
+    }
+
+    pub fn to_life106(&self) -> String {
+        // Writes the alive cells in Life 1.06 format, row by row,
+        // using their stable (Life trait) coordinates.
+
+        let mut output = format!("{}\n", LIFE106_HEADER);
+        for y in 1..=self.height {
+            for x in 1..=self.width {
+                if self.board[x][y].alive {
+                    let (x, y) = (x as i64 + self.offset.0, y as i64 + self.offset.1);
+                    output.push_str(&format!("{} {}\n", x, y));
+                }
+            }
+        }
+        output
+    }
+
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        // Writes everything needed to carry on a run later: the size,
+        // rule, wrapping and generation, then the alive cells one per
+        // line, row by row. Read back with load.
+        writeln!(writer, "{} {}", SAVE_HEADER, SAVE_VERSION)?;
+        writeln!(writer, "width {}", self.width)?;
+        writeln!(writer, "height {}", self.height)?;
+        writeln!(writer, "rule {}", self.rule)?;
+        writeln!(writer, "wrap {}", self.wrap)?;
+        writeln!(writer, "generation {}", self.generation)?;
+        writeln!(writer, "alive")?;
+        for (x, y) in self.live_cells() {
+            writeln!(writer, "{} {}", x, y)?;
+        }
+        writer.flush()
+    }
+
+    pub fn load<R: Read>(reader: R) -> Result<Board, BoardError> {
+        // Reads a board written by save. A file from a newer version of
+        // the format is UnsupportedVersion, one that's malformed is
+        // Corrupt, saying what's wrong with it, and one with a size of 0
+        // or past MAX_LOADED_SIZE is Invalid.
+        let invalid = BoardError::Corrupt;
+        let mut lines = io::BufReader::new(reader).lines();
+
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let version: u32 = header
+            .strip_prefix(SAVE_HEADER)
+            .and_then(|version| version.trim().parse().ok())
+            .ok_or_else(|| invalid(format!("not a saved board: {:?}", header)))?;
+        if version != SAVE_VERSION {
+            return Err(BoardError::UnsupportedVersion {
+                found: version,
+                supported: SAVE_VERSION,
+            });
+        }
+
+        let mut field = |name: &str| {
+            let line = lines
+                .next()
+                .transpose()?
+                .ok_or_else(|| invalid(format!("missing {}", name)))?;
+            match line.split_once(' ') {
+                Some((key, value)) if key == name => Ok(value.to_string()),
+                _ => Err(invalid(format!("expected {}, found {:?}", name, line))),
+            }
+        };
+        let number = |name: &str, value: String| {
+            value
+                .parse::<u64>()
+                .map_err(|_| invalid(format!("{} {:?} isn't a number", name, value)))
+        };
+        let width = number("width", field("width")?)? as usize;
+        let height = number("height", field("height")?)? as usize;
+        let rule: Rule = field("rule")?.parse()?;
+        let wrap = match field("wrap")?.as_str() {
+            "true" => true,
+            "false" => false,
+            other => return Err(invalid(format!("wrap {:?} isn't true or false", other))),
+        };
+        let generation = number("generation", field("generation")?)?;
+        check_loaded_size(width, height)?;
+
+        match lines.next().transpose()? {
+            Some(line) if line == "alive" => (),
+            line => return Err(invalid(format!("expected alive, found {:?}", line))),
+        }
+        let mut cells = Vec::new();
+        for line in lines {
+            let line = line?;
+            let cell = line
+                .split_once(' ')
+                .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)));
+            cells.push(cell.ok_or_else(|| invalid(format!("expected a cell, found {:?}", line)))?);
+        }
+
+        let mut board = Board::with_size(width, height);
+        board.set_rule(rule);
+        board.set_wrap(wrap);
+        board.set_cells(&cells, true)?;
+        board.generation = generation;
+        Ok(board)
+    }
+
+    pub fn write_snapshot<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        // Writes the board in a compact binary layout, for boards too big
+        // to save as text. After SNAPSHOT_MAGIC and the version byte come
+        // the width and height as little-endian u32s, the rule's length
+        // as a u8 followed by the rule, and the generation as a
+        // little-endian u64. Then one bit per cell, row by row from the
+        // top-left, starting from the lowest bit of each byte, with the
+        // last byte padded with zeroes. Read back with read_snapshot.
+        let too_large = |what: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is too large for a snapshot", what),
+            )
+        };
+        let width = u32::try_from(self.width).map_err(|_| too_large("width"))?;
+        let height = u32::try_from(self.height).map_err(|_| too_large("height"))?;
+        let rule = self.rule.to_string();
+        let rule_length = u8::try_from(rule.len()).map_err(|_| too_large("rule"))?;
+
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        writer.write_all(&[SNAPSHOT_VERSION])?;
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        writer.write_all(&[rule_length])?;
+        writer.write_all(rule.as_bytes())?;
+        writer.write_all(&self.generation.to_le_bytes())?;
+
+        let mut cells = vec![0u8; (self.width * self.height).div_ceil(8)];
+        for y in 1..=self.height {
+            for x in 1..=self.width {
+                if self.board[x][y].alive {
+                    let i = (y - 1) * self.width + (x - 1);
+                    cells[i / 8] |= 1 << (i % 8);
+                }
+            }
+        }
+        writer.write_all(&cells)?;
+        writer.flush()
+    }
+
+    pub fn read_snapshot<R: Read>(mut reader: R) -> Result<Board, BoardError> {
+        // Reads a board written by write_snapshot. A snapshot from a newer
+        // version of the layout is UnsupportedVersion, and one that's
+        // corrupted or cut short is Corrupt, saying what's wrong with it.
+        let invalid = BoardError::Corrupt;
+        let mut read = |buffer: &mut [u8], what: &str| {
+            reader.read_exact(buffer).map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => {
+                    invalid(format!("snapshot ends before its {}", what))
+                }
+                _ => BoardError::Io(e),
+            })
+        };
+
+        let mut magic = [0; 4];
+        read(&mut magic, "header")?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(invalid(format!("not a board snapshot: starts {:?}", magic)));
+        }
+        let mut version = [0; 1];
+        read(&mut version, "version")?;
+        if version[0] != SNAPSHOT_VERSION {
+            return Err(BoardError::UnsupportedVersion {
+                found: version[0].into(),
+                supported: SNAPSHOT_VERSION.into(),
+            });
+        }
+        let (mut width, mut height) = ([0; 4], [0; 4]);
+        read(&mut width, "size")?;
+        read(&mut height, "size")?;
+        let (width, height) = (
+            u32::from_le_bytes(width) as usize,
+            u32::from_le_bytes(height) as usize,
+        );
+        if width == 0 || height == 0 {
+            return Err(GolError::InvalidSize { width, height }.into());
+        }
+        let mut rule_length = [0; 1];
+        read(&mut rule_length, "rule")?;
+        let mut rule = vec![0; rule_length[0] as usize];
+        read(&mut rule, "rule")?;
+        let rule: Rule = String::from_utf8(rule)
+            .map_err(|_| invalid("snapshot's rule isn't UTF-8".to_string()))?
+            .parse()?;
+        let mut generation = [0; 8];
+        read(&mut generation, "generation")?;
+
+        // Read before the board is made, so a corrupted size can't make
+        // it allocate more than the snapshot holds
+        let length = width
+            .checked_mul(height)
+            .map(|cells| cells.div_ceil(8))
+            .ok_or_else(|| invalid(format!("{}x{} is too many cells", width, height)))?;
+        let mut cells = Vec::new();
+        reader
+            .by_ref()
+            .take(length as u64)
+            .read_to_end(&mut cells)?;
+        if cells.len() < length {
+            return Err(invalid(format!(
+                "snapshot ends after {} of its {} bytes of cells",
+                cells.len(),
+                length
+            )));
+        }
+        if reader.read(&mut [0])? > 0 {
+            return Err(invalid("snapshot has data after its cells".to_string()));
+        }
+
+        let mut board = Board::with_size(width, height);
+        board.set_rule(rule);
+        for y in 1..=height {
+            for x in 1..=width {
+                let i = (y - 1) * width + (x - 1);
+                if cells[i / 8] >> (i % 8) & 1 == 1 {
+                    board.set_alive_unchecked((x, y), true);
+                }
+            }
+        }
+        board.generation = u64::from_le_bytes(generation);
+        Ok(board)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn save_ron(&self, path: &Path) -> io::Result<()> {
+        // Writes the board's size, generation, rule and alive cells to
+        // `path` as RON. Other settings, such as wrapping, aren't saved.
+        let mut alive = Vec::new();
+        for y in 1..=self.height {
+            let mut x = 1;
+            while x <= self.width {
+                let length = (x..=self.width)
+                    .take_while(|&x| self.board[x][y].alive)
+                    .count();
+                if length > 0 {
+                    alive.push(Run { x, y, length });
+                }
+                x += length + 1;
+            }
+        }
+        let saved = RonBoard {
+            width: self.width,
+            height: self.height,
+            generation: self.generation,
+            rule: self.rule.to_string(),
+            alive,
+        };
+        let config = ron::ser::PrettyConfig::new().struct_names(true);
+        let text = ron::ser::to_string_pretty(&saved, config).map_err(io::Error::other)?;
+        std::fs::write(path, text + "\n")
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load_ron(path: &Path) -> Result<Board, BoardError> {
+        // Reads a board written by save_ron. A file that isn't valid RON
+        // or has cells off the board is Corrupt, and one with an unknown
+        // rule, or a size of 0 or past MAX_LOADED_SIZE, is Invalid.
+        let text = std::fs::read_to_string(path)?;
+        let saved: RonBoard =
+            ron::from_str(&text).map_err(|e| BoardError::Corrupt(e.to_string()))?;
+        let rule: Rule = saved.rule.parse()?;
+        check_loaded_size(saved.width, saved.height)?;
+
+        let mut board = Board::with_size(saved.width, saved.height);
+        board.set_rule(rule);
+        for run in &saved.alive {
+            let end = run.x.checked_add(run.length).map(|end| end - 1);
+            match end {
+                Some(end)
+                    if run.x >= 1 && end <= saved.width && (1..=saved.height).contains(&run.y) =>
+                {
+                    for x in run.x..=end {
+                        board.set_alive_unchecked((x, run.y), true);
+                    }
+                }
+                _ => {
+                    return Err(BoardError::Corrupt(format!(
+                        "run of {} cells at ({}, {}) is off the {}x{} board",
+                        run.length, run.x, run.y, saved.width, saved.height
+                    )))
+                }
+            }
+        }
+        board.generation = saved.generation;
+        Ok(board)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn save_json(&self, path: &Path) -> io::Result<()> {
+        // Writes the board's size, rule, wrapping, border, generation
+        // and alive cells to `path` as compact JSON, the cells as a list
+        // of [x, y] pairs
+        let mut writer = io::BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn load_json(path: &Path) -> Result<Board, BoardError> {
+        // Reads a board written by save_json. A file that isn't valid
+        // JSON, or describes a board with no cells or cells off it, is
+        // Corrupt.
+        let reader = io::BufReader::new(File::open(path)?);
+        serde_json::from_reader(reader).map_err(|e| {
+            if e.is_io() {
+                BoardError::Io(e.into())
+            } else {
+                BoardError::Corrupt(e.to_string())
+            }
+        })
+    }
+
+    #[cfg(feature = "image")]
+    pub fn from_image(path: &Path) -> Result<Board, ImageLoadError> {
+        // Reads a board from a PNG or BMP image, one cell per pixel, so
+        // the board is as wide and high as the image, however large.
+        // Each pixel is taken as greyscale: darker than mid-grey
+        // (brightness below 128 of 255) is alive, anything lighter is
+        // dead. Pixels that are more than half transparent are dead
+        // whatever their colour, so patterns can be drawn on a clear
+        // background.
+        let image = image::open(path)?.to_luma_alpha8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        if width == 0 || height == 0 {
+            return Err(ImageLoadError::Empty);
+        }
+        let mut board = Board::with_size(width, height);
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let [brightness, alpha] = pixel.0;
+            if brightness < 128 && alpha >= 128 {
+                board.set_alive_unchecked((x as usize + 1, y as usize + 1), true);
+            }
+        }
+        Ok(board)
+    }
+
+    pub fn to_grid(&self) -> Vec<Vec<bool>> {
+        // Whether each cell is alive, as `height` rows of `width` cells,
+        // so grid[y][x] is the cell at (x + 1, y + 1)
+        (1..=self.height)
+            .map(|y| (1..=self.width).map(|x| self.board[x][y].alive).collect())
+            .collect()
+    }
+
+    pub fn from_grid(grid: &[Vec<bool>]) -> Result<Board, GolError> {
+        // Builds a board from rows of cells laid out as by to_grid.
+        // Every row must be as long as the first.
+
+        let width = grid.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(GolError::InvalidSize {
+                width,
+                height: grid.len(),
+            });
+        }
+        if let Some((row, cells)) = grid
+            .iter()
+            .enumerate()
+            .find(|(_, cells)| cells.len() != width)
+        {
+            return Err(GolError::RaggedGrid {
+                row,
+                expected: width,
+                found: cells.len(),
+            });
+        }
+
+        let mut board = Board::with_size(width, grid.len());
+        for (y, cells) in grid.iter().enumerate() {
+            for (x, &alive) in cells.iter().enumerate() {
+                board.set_alive_unchecked((x + 1, y + 1), alive);
+            }
+        }
+        Ok(board)
+    }
+
+    pub fn from_rows(rows: &[&str]) -> Result<Board, GolError> {
+        // Builds a board with one row per string, '#' for an alive cell
+        // and '.' for a dead one. Unlike from_ascii, every row must be
+        // the same length and hold nothing else, which suits tests.
+        let grid = rows
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.chars()
+                    .map(|c| match c {
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err(GolError::Parse {
+                            line: y + 1,
+                            message: format!("{:?} is neither '#' nor '.'", c),
+                        }),
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<bool>>, GolError>>()?;
+        Board::from_grid(&grid)
+    }
+
+    pub fn from_ascii(art: &str, alive_char: char) -> Result<Board, GolError> {
+        // Builds a board from text with one line per row, as wide as
+        // the longest line. `alive_char` marks an alive cell and any
+        // other character a dead one, so short lines end in dead
+        // cells. Blank lines before and after the art are left out.
+
+        let mut rows: Vec<&str> = art
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .collect();
+        while rows.last().is_some_and(|line| line.trim().is_empty()) {
+            rows.pop();
+        }
+        let width = rows.iter().map(|row| row.chars().count()).max();
+        let grid: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| {
+                let mut cells: Vec<bool> = row.chars().map(|c| c == alive_char).collect();
+                cells.resize(width.unwrap_or(0), false);
+                cells
+            })
+            .collect();
+        Board::from_grid(&grid)
+    }
+}
+
+impl Default for Board {
+    fn default() -> Board {
+        Board::new()
+    }
+}
+
+// Boards are equal when they are the same size and the same cells are
+// alive. The generation, offset and growth limit don't take part.
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.alive_states().eq(other.alive_states())
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        for alive in self.alive_states() {
+            alive.hash(state);
+        }
+    }
+}
+
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The size and generation, then the cells as Display draws
+        // them, so a failed assert_eq shows both boards
+        writeln!(
+            f,
+            "Board {}x{} at generation {}",
+            self.width, self.height, self.generation
+        )?;
+        write!(f, "{}", self)
+    }
+}
+
+// Reads back what Display writes with the default glyphs, or the same
+// grid drawn with '#' for alive and '.' for dead cells. Every line must
+// be as long as the first; newlines at the end are ignored.
+impl FromStr for Board {
+    type Err = GolError;
+
+    fn from_str(text: &str) -> Result<Board, GolError> {
+        let mut grid: Vec<Vec<bool>> = Vec::new();
+        for (i, line) in text.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let parse_error = |message| GolError::Parse {
+                line: i + 1,
+                message,
+            };
+            let cells = line
+                .chars()
+                .map(|c| match c {
+                    ALIVE_GLYPH | '#' => Ok(true),
+                    DEAD_GLYPH | '.' => Ok(false),
+                    _ => Err(parse_error(format!("unexpected character {:?}", c))),
+                })
+                .collect::<Result<Vec<bool>, GolError>>()?;
+            if let Some(first) = grid.first() {
+                if cells.len() != first.len() {
+                    return Err(parse_error(format!(
+                        "{} cells, but the first line has {}",
+                        cells.len(),
+                        first.len()
+                    )));
+                }
+            }
+            grid.push(cells);
+        }
+        Board::from_grid(&grid)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (alive, dead) = self.glyphs;
+        for ((x, _), cell) in self.iter() {
+            write!(f, "{}", if cell { alive } else { dead })?;
+            if x == self.width {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Life for Board {
+    fn step(&mut self) {
+        self.tick();
+    }
+
+    fn is_alive_at(&self, coords: (i64, i64)) -> bool {
+        match self
+            .board_coords(coords)
+            .and_then(|coords| self.get_cell(coords))
+        {
+            Some(cell) => cell.alive,
+            None => self.background,
+        }
+    }
+
+    fn set_alive_at(&mut self, coords: (i64, i64), alive: bool) {
+        if let Some(coords) = self.board_coords(coords) {
+            if self.get_cell(coords).is_some() {
+                self.set_alive_unchecked(coords, alive);
+            }
+        }
+    }
+
+    fn flip_at(&mut self, coords: (i64, i64)) {
+        if let Some(coords) = self.board_coords(coords) {
+            if let Some(cell) = self.get_cell(coords) {
+                let alive = !cell.alive;
+                self.set_alive_unchecked(coords, alive);
+            }
+        }
+    }
+
+    fn undo(&mut self) -> bool {
+        let current = self.snapshot();
+        match self.history.undo(current) {
+            Some(previous) => {
+                self.restore(&previous);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn redo(&mut self) -> bool {
+        let current = self.snapshot();
+        match self.history.redo(current) {
+            Some(next) => {
+                self.restore(&next);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn population(&self) -> usize {
+        // O(1) unless cells have been edited through get_cell_mut
+        // since the last tick
+        self.population.unwrap_or_else(|| self.count_population())
+    }
+
+    fn step_with_stats(&mut self) -> Option<TickStats> {
+        Some(self.tick())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rand::Rng;
+    use std::vec;
+
+    fn setup() -> (usize, usize, Board, rand::prelude::ThreadRng) {
+        let mut rng = rand::thread_rng();
+        let board = Board::new();
+        let x_coord = rng.gen_range(1..BOARD_WIDTH);
+        let y_coord = rng.gen_range(1..BOARD_HEIGHT);
+        (x_coord, y_coord, board, rng)
+    }
+
+    #[test]
+    fn cell_flip() {
+        let (x_coord, y_coord, mut board, _rng) = setup();
+        let cell = board.get_cell_mut((x_coord, y_coord)).unwrap();
+        cell.flip();
+
+        assert!(board.get_cell((x_coord, y_coord)).unwrap().alive)
+    }
+
+    #[test]
+    fn board_get_cell() {
+        let (x_coord, y_coord, mut board, _rng) = setup();
+        board.get_cell_mut((x_coord, y_coord)).unwrap().alive = true;
+        assert!(board.get_cell((x_coord, y_coord)).unwrap().alive);
+    }
+
+    #[test]
+    fn board_cell_is_one_byte() {
+        assert_eq!(std::mem::size_of::<Cell>(), 1);
+    }
+
+    #[test]
+    fn boarder_1() {
+        let (_x_coord, _y_coord, board, _rng) = setup();
+        assert_eq!(board.get_cell((0, 0)), None);
+    }
+
+    #[test]
+    fn boarder_2() {
+        let (_x_coord, _y_coord, board, _rng) = setup();
+        assert_eq!(board.get_cell((BOARD_WIDTH, BOARD_HEIGHT)), None);
+    }
+
+    #[test]
+    fn board_count_adjacent_alive_1() {
+        let (_x_coord, _y_coord, board, _rng) = setup();
+        board.count_adjacent_alive((1, 1)); //Should not panic
+    }
+
+    #[test]
+    fn board_count_adjacent_alive_2() {
+        let (x_coord, y_coord, mut board, mut rng) = setup();
+
+        // Randomly select three adjacent cells to make alive
+        let targets = [
+            rng.gen_range(0..9),
+            rng.gen_range(0..9),
+            rng.gen_range(0..9),
+        ];
+
+        let mut counter = 0;
+        for target in targets.iter() {
+            match target {
+                1 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord - 1, y_coord - 1)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                2 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord - 1, y_coord)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                3 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord - 1, y_coord + 1)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                4 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord, y_coord - 1)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                5 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord, y_coord + 1)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                6 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord + 1, y_coord - 1)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                7 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord + 1, y_coord)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                8 => {
+                    if let Some(cell) = board.get_cell_mut((x_coord + 1, y_coord + 1)) {
+                        if !cell.alive {
+                            cell.alive = true;
+                            counter += 1;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        assert_eq!(board.count_adjacent_alive((x_coord, y_coord)), counter)
+    }
+
+    #[test]
+    fn board_get_cells_to_flip_1() {
+        let (_x_coord, _y_coord, mut board, mut rng) = setup();
+
+        let coords1: (usize, usize) = (
+            rng.gen_range(1..BOARD_WIDTH),
+            rng.gen_range(1..BOARD_HEIGHT),
+        );
+        let coords2: (usize, usize) = (
+            rng.gen_range(1..BOARD_WIDTH),
+            rng.gen_range(1..BOARD_HEIGHT),
+        );
+        let coords3: (usize, usize) = (
+            rng.gen_range(1..BOARD_WIDTH),
+            rng.gen_range(1..BOARD_HEIGHT),
+        );
+
+        for coords in [coords1, coords2, coords3].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+
+        let mut flipped = vec![coords1, coords2, coords3];
+        let mut test_result = board.get_cells_to_flip();
+        flipped.sort();
+        test_result.sort();
+        assert_eq!(flipped, test_result);
+    }
+
+    #[test]
+    fn board_get_cells_to_flip_2() {
+        let board = Board::from_rows(&["......", "......", "......", "...#..", "...##.", "......"])
+            .unwrap();
+
+        let to_flip: Vec<(usize, usize)> = vec![(5, 4)];
+        let test_result = board.get_cells_to_flip();
+        assert_eq!(to_flip, test_result);
+    }
+
+    #[test]
+    fn board_get_cells_to_flip_3() {
+        // A lone cell in the corner
+        let board = Board::from_rows(&["#..", "...", "..."]).unwrap();
+
+        let to_flip: Vec<(usize, usize)> = vec![(1, 1)];
+        let test_result = board.get_cells_to_flip();
+        assert_eq!(to_flip, test_result);
+    }
+
+    #[test]
+    fn board_get_cells_to_flip_4() {
+        let mut board = Board::from_rows(&["##.", "##.", "..."]).unwrap();
+
+        let to_flip: Vec<(usize, usize)> = vec![];
+        let test_result = board.get_cells_to_flip();
+        assert_eq!(to_flip, test_result);
+        let start = board.clone();
+        board.tick();
+        assert_eq!(board, start);
+    }
+
+    #[test]
+    fn board_get_cells_to_flip_5() {
+        let mut board = Board::from_rows(&["###", "###", "..."]).unwrap();
+
+        let mut to_flip: Vec<(usize, usize)> = vec![(2, 1), (2, 2), (2, 3)];
+        let mut test_result = board.get_cells_to_flip();
+        to_flip.sort();
+        test_result.sort();
+        assert_eq!(to_flip, test_result);
+        board.tick();
+        assert_eq!(board, Board::from_rows(&["#.#", "#.#", ".#."]).unwrap());
+    }
+
+    #[test]
+    fn board_record_gif() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        let path = std::env::temp_dir().join("gameoflife-rs-board_record_gif.gif");
+
+        // Blinker, so consecutive frames differ
+        for coords in [(4, 3), (4, 4), (4, 5)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        board.record_gif(3, 10, &path).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = decoder.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!(decoder.width() as usize, (BOARD_WIDTH - 1) * PIXEL_SCALE);
+        assert_eq!(decoder.height() as usize, (BOARD_HEIGHT - 1) * PIXEL_SCALE);
+
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            frames.push(frame.buffer.to_vec());
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames.len(), 3);
+        assert_ne!(frames[0], frames[1]);
+        assert_eq!(frames[0], frames[2]);
+    }
+
+    #[test]
+    fn board_grow_keeps_coordinates() {
+        let mut board = Board::with_size(10, 10);
+        board.set_grow(Some((100, 100)));
+
+        // Blinker against the top-left corner
+        for coords in [(1, 1), (2, 1), (3, 1)].iter() {
+            board.set_alive_at(*coords, true);
+        }
+        board.tick();
+
+        assert_eq!(board.width(), 10 + GROW_MARGIN);
+        assert_eq!(board.height(), 10 + GROW_MARGIN);
+        assert_eq!(
+            board.offset(),
+            (-(GROW_MARGIN as i64), -(GROW_MARGIN as i64))
+        );
+        for coords in [(2, 0), (2, 1), (2, 2)].iter() {
+            assert!(board.is_alive_at(*coords));
+        }
+        assert_eq!(board.population(), 3);
+    }
+
+    #[test]
+    fn board_grow_limit() {
+        let mut board = Board::with_size(10, 10);
+        board.set_grow(Some((20, 12)));
+        for coords in [(1, 1), (2, 1), (3, 1)].iter() {
+            board.set_alive_at(*coords, true);
+        }
+        board.tick();
+
+        assert_eq!(board.width(), 20);
+        assert_eq!(board.height(), 12);
+    }
+
+    #[test]
+    fn board_grow_r_pentomino() {
+        let mut board = Board::with_size(20, 20);
+        board.set_grow(Some((1000, 1000)));
+        for coords in [(10, 9), (11, 9), (9, 10), (10, 10), (10, 11)].iter() {
+            board.set_alive_at(*coords, true);
+        }
+
+        // The R-pentomino stabilizes at generation 1103, having thrown
+        // off six gliders that travel well beyond the starting board
+        for _ in 0..1103 {
+            board.tick();
+        }
+        assert!(board.width() > 200 && board.height() > 200);
+        assert_eq!(board.population(), 116);
+    }
+
+    #[test]
+    fn board_copy_region() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        for coords in [(5, 4), (6, 5), (4, 6), (5, 6), (6, 6)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+
+        let region = board.copy_region(4, 4, 7, 6);
+        assert_eq!((region.width(), region.height()), (4, 3));
+        let mut alive: Vec<(usize, usize)> = Vec::new();
+        for x in 1..=4 {
+            for y in 1..=3 {
+                if region.get_cell((x, y)).unwrap().alive {
+                    alive.push((x, y));
+                }
+            }
+        }
+        assert_eq!(alive, vec![(1, 3), (2, 1), (2, 3), (3, 2), (3, 3)]);
+
+        // The corners can be given in any order
+        assert_eq!(board.copy_region(7, 6, 4, 4), region);
+        assert_eq!(board.copy_region(4, 6, 7, 4), region);
+        assert_eq!(board.copy_region(5, 5, 1, 1), board.copy_region(1, 1, 5, 5));
+    }
+
+    #[test]
+    fn board_copy_region_past_edge() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        board.get_cell_mut((BOARD_WIDTH - 1, 1)).unwrap().flip();
+
+        let region = board.copy_region(BOARD_WIDTH - 1, 1, BOARD_WIDTH + 1, 2);
+        assert_eq!((region.width(), region.height()), (3, 2));
+        assert_eq!(region.population(), 1);
+        assert!(region.get_cell((1, 1)).unwrap().alive);
+    }
+
+    #[test]
+    fn board_paste_region() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        let mut src = Board::with_size(2, 2);
+        src.get_cell_mut((1, 1)).unwrap().flip();
+
+        // Pasting ORs the cells in, so (11, 10) stays alive
+        board.get_cell_mut((11, 10)).unwrap().flip();
+        board.paste_region(&src, (10, 10));
+        assert!(board.get_cell((10, 10)).unwrap().alive);
+        assert!(board.get_cell((11, 10)).unwrap().alive);
+        assert_eq!(board.population(), 2);
+
+        // Cells past the edge are dropped
+        board.paste_region(&src, (BOARD_WIDTH, BOARD_HEIGHT));
+        assert_eq!(board.population(), 2);
+    }
+
+    #[test]
+    fn board_tick_matches_cells_to_flip() {
+        let (_x_coord, _y_coord, mut board, mut rng) = setup();
+        for x in 1..BOARD_WIDTH {
+            for y in 1..BOARD_HEIGHT {
+                board.get_cell_mut((x, y)).unwrap().alive = rng.gen_bool(0.4);
+            }
+        }
+
+        // Ticking must flip exactly the cells get_cells_to_flip reports
+        for _ in 0..10 {
+            let mut expected = board.copy_region(1, 1, BOARD_WIDTH - 1, BOARD_HEIGHT - 1);
+            for coords in board.get_cells_to_flip().iter() {
+                expected.get_cell_mut(*coords).unwrap().flip();
+            }
+            board.tick();
+            assert_eq!(board.to_string(), expected.to_string());
+        }
+    }
+
+    #[test]
+    fn board_clear() {
+        let (x_coord, y_coord, mut board, _rng) = setup();
+        board.get_cell_mut((x_coord, y_coord)).unwrap().flip();
+        board.tick();
+        assert_eq!(board.generation(), 1);
+
+        board.clear();
+        assert_eq!(board.generation(), 0);
+        assert_eq!(board.population(), 0);
+    }
+
+    #[test]
+    fn board_entropy() {
+        let mut board = Board::with_size(4, 2);
+        assert_eq!(board.entropy(), 0.0);
+        board.set_rect(1, 1, 4, 1, true);
+        assert_eq!(board.entropy(), 1.0);
+        board.fill();
+        assert_eq!(board.entropy(), 0.0);
+        board.clear_rect(1, 1, 2, 1);
+        assert_eq!(board.entropy(), stats::entropy(6, 8));
+    }
+
+    #[test]
+    fn board_fill() {
+        let (x_coord, y_coord, mut board, _rng) = setup();
+        board.get_cell_mut((x_coord, y_coord)).unwrap().flip();
+        board.tick();
+
+        board.fill();
+        assert_eq!(board.generation(), 1);
+        assert_eq!(board.population(), board.width * board.height);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+        let mut inverted = Board::with_size(board.width, board.height);
+        inverted.invert();
+        assert_eq!(board.live_cells().count(), inverted.population());
+        assert_eq!(board.zobrist_hash(), inverted.zobrist_hash());
+
+        board.invert();
+        assert_eq!(board.population(), 0);
+        assert_eq!(board.zobrist_hash(), 0);
+    }
+
+    #[test]
+    fn board_reset_random() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        board.tick();
+
+        board.reset_random(7, 0.5);
+        assert_eq!(board.generation(), 0);
+        let first = board.to_string();
+        board.reset_random(7, 0.5);
+        assert_eq!(board.to_string(), first);
+        board.reset_random(8, 0.5);
+        assert_ne!(board.to_string(), first);
+
+        board.reset_random(7, 0.0);
+        assert_eq!(board.population(), 0);
+        board.reset_random(7, 1.0);
+        assert_eq!(board.population(), (BOARD_WIDTH - 1) * (BOARD_HEIGHT - 1));
+    }
+
+    #[test]
+    fn board_get_cells_to_flip_candidates() {
+        // Restricting the search to candidate cells must give exactly
+        // the same flip list as checking every cell
+        for &density in [0.02, 0.2, 0.5].iter() {
+            let (_x_coord, _y_coord, mut board, mut rng) = setup();
+            for x in 1..BOARD_WIDTH {
+                for y in 1..BOARD_HEIGHT {
+                    board.get_cell_mut((x, y)).unwrap().alive = rng.gen_bool(density);
+                }
+            }
+            for _ in 0..5 {
+                assert_eq!(board.get_cells_to_flip(), board.cells_to_flip(None));
+                board.tick();
+            }
+        }
+    }
+
+    #[test]
+    fn board_eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(board: &Board) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        for coords in [(4, 3), (4, 4), (4, 5)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+
+        let clone = board.clone();
+        assert_eq!(board, clone);
+        assert_eq!(hash(&board), hash(&clone));
+
+        // A blinker isn't static, so one tick changes it
+        board.tick();
+        assert_ne!(board, clone);
+        assert_ne!(hash(&board), hash(&clone));
+
+        // Back in phase after a second tick, even though the
+        // generation counters differ
+        board.tick();
+        assert_eq!(board, clone);
+        assert_eq!(hash(&board), hash(&clone));
+
+        // Different sizes never compare equal
+        assert_ne!(Board::with_size(3, 4), Board::with_size(4, 3));
+    }
+
+    #[test]
+    fn board_undo_redo() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        for coords in [(5, 4), (6, 5), (4, 6), (5, 6), (6, 6)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        assert!(!board.can_undo());
+
+        let start = board.clone();
+        board.tick();
+        let after_one = board.clone();
+        board.tick();
+        assert!(board.can_undo());
+        assert!(!board.can_redo());
+
+        assert!(board.undo());
+        assert_eq!(board, after_one);
+        assert_eq!(board.generation(), 1);
+        assert!(board.undo());
+        assert_eq!(board, start);
+        assert_eq!(board.generation(), 0);
+        assert!(!board.undo());
+
+        assert!(board.redo());
+        assert_eq!(board, after_one);
+        assert!(board.can_redo());
+
+        // Ticking forward again abandons what was undone
+        board.tick();
+        assert!(!board.can_redo());
+    }
+
+    #[test]
+    fn board_undo_growth() {
+        let mut board = Board::with_size(10, 10);
+        board.set_grow(Some((100, 100)));
+        for coords in [(1, 1), (2, 1), (3, 1)].iter() {
+            board.set_alive_at(*coords, true);
+        }
+        let start = board.clone();
+
+        board.tick();
+        assert_eq!(board.width(), 10 + GROW_MARGIN);
+        assert!(board.undo());
+        assert_eq!(board, start);
+        assert_eq!(board.offset(), (0, 0));
+    }
+
+    #[test]
+    fn board_history_depth() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        board.set_history_depth(2);
+        for _ in 0..5 {
+            board.tick();
+        }
+        assert!(board.undo());
+        assert!(board.undo());
+        assert!(!board.undo());
+        assert_eq!(board.generation(), 3);
+    }
+
+    #[test]
+    fn board_find_islands() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        // Glider, lower down but further left than the blinker
+        let glider = [(2, 10), (3, 11), (1, 12), (2, 12), (3, 12)];
+        // Vertical blinker
+        let blinker = [(20, 2), (20, 3), (20, 4)];
+        // Two cells touching only at a corner, against the right edge
+        let diagonal = [(BOARD_WIDTH - 2, 5), (BOARD_WIDTH - 1, 6)];
+        for coords in glider.iter().chain(blinker.iter()).chain(diagonal.iter()) {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+
+        let islands = board.find_islands();
+        assert_eq!(
+            islands,
+            vec![
+                blinker.to_vec(),
+                diagonal.to_vec(),
+                vec![(2, 10), (3, 11), (1, 12), (2, 12), (3, 12)],
+            ]
+        );
+        assert!(Board::new().find_islands().is_empty());
+    }
+
+    fn assert_parallel_matches_serial(parallelism: Parallelism) {
+        // Widths that don't divide evenly between threads, and one
+        // narrower than the number of threads
+        for &(seed, width) in [(1, 300), (2, 101), (3, 7)].iter() {
+            let mut serial = Board::with_size(width, 50);
+            serial.set_history_depth(0);
+            serial.reset_random(seed, 0.4);
+            let mut parallel = serial.clone();
+            parallel.set_parallelism(parallelism);
+
+            for _ in 0..50 {
+                serial.tick();
+                parallel.tick();
+                assert_eq!(parallel, serial, "{:?}, seed {}", parallelism, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn board_strips_match_serial() {
+        for &threads in [1, 2, 3, 4, 8].iter() {
+            assert_parallel_matches_serial(Parallelism::Strips(Some(threads)));
+        }
+        assert_parallel_matches_serial(Parallelism::Strips(None));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn board_rayon_matches_serial() {
+        assert_parallel_matches_serial(Parallelism::Rayon);
+    }
+
+    #[test]
+    fn board_life106_round_trip() {
+        let input = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+        let board = Board::from_life106(input).unwrap();
+        assert_eq!(board.width(), 3 + 2 * PATTERN_MARGIN);
+        assert_eq!(board.height(), 3 + 2 * PATTERN_MARGIN);
+        assert_eq!(board.population(), 5);
+        assert!(board.is_alive_at((0, -1)));
+        assert!(board.is_alive_at((-1, 1)));
+        assert!(
+            board
+                .get_cell((PATTERN_MARGIN + 1, PATTERN_MARGIN + 3))
+                .unwrap()
+                .alive
+        );
+        assert_eq!(board.to_life106(), input);
+
+        // Comments and blank lines are skipped
+        let commented =
+            Board::from_life106("\n#Life 1.06\n#D glider\n\n0 -1\n1 0\n-1 1\n0 1\n1 1").unwrap();
+        assert_eq!(commented, board);
+    }
+
+    #[test]
+    fn board_save_round_trip() {
+        let mut board = Board::with_size(13, 9);
+        board.set_rule("B36/S23".parse().unwrap());
+        board.set_wrap(true);
+        board.reset_random(6, 0.4);
+        board.tick_n(17);
+
+        let mut saved = Vec::new();
+        board.save(&mut saved).unwrap();
+        let text = String::from_utf8(saved.clone()).unwrap();
+        assert!(text.starts_with(
+            "gameoflife-rs board 1\nwidth 13\nheight 9\nrule B36/S23\nwrap true\ngeneration 17\nalive\n"
+        ));
+
+        let loaded = Board::load(&saved[..]).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.rule(), board.rule());
+        assert!(loaded.wrap());
+        assert_eq!(loaded.generation(), 17);
+        assert_eq!(loaded.zobrist_hash(), board.zobrist_hash());
+    }
+
+    #[test]
+    fn board_load_errors() {
+        let error = |text: &str| Board::load(text.as_bytes()).unwrap_err().to_string();
+        let body = "width 3\nheight 2\nrule B3/S23\nwrap false\ngeneration 0\nalive\n";
+
+        assert_eq!(
+            error(&format!("gameoflife-rs board 2\n{}", body)),
+            "saved in format version 2, but only version 1 can be read"
+        );
+        assert_eq!(error(""), "not a saved board: \"\"");
+        assert_eq!(error("gameoflife-rs board 1\nwidth 3\n"), "missing height");
+        assert_eq!(
+            error("gameoflife-rs board 1\nheight 2\n"),
+            "expected width, found \"height 2\""
+        );
+        assert_eq!(
+            error(&format!("gameoflife-rs board 1\n{}4 1\n", body)),
+            "(4, 1) is off a 3x2 board, whose cells run from (1, 1) to (3, 2)"
+        );
+        assert_eq!(
+            error(&format!("gameoflife-rs board 1\n{}1\n", body)),
+            "expected a cell, found \"1\""
+        );
+        assert!(Board::load(format!("gameoflife-rs board 1\n{}2 2\n", body).as_bytes()).is_ok());
+
+        // Each kind of problem is its own variant
+        let load = |text: String| Board::load(text.as_bytes()).unwrap_err();
+        assert!(matches!(
+            load(format!("gameoflife-rs board 2\n{}", body)),
+            BoardError::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            }
+        ));
+        assert!(matches!(
+            load("gameoflife-rs board 1\nwidth 3\n".to_string()),
+            BoardError::Corrupt(_)
+        ));
+        assert!(matches!(
+            load(format!("gameoflife-rs board 1\n{}4 1\n", body)),
+            BoardError::OutOfBounds(OutOfBoundsError {
+                coords: (4, 1),
+                board: (3, 2)
+            })
+        ));
+        assert!(matches!(
+            load(format!(
+                "gameoflife-rs board 1\n{}",
+                body.replace("B3/", "B9/")
+            )),
+            BoardError::Invalid(GolError::InvalidRule(_))
+        ));
+        assert!(matches!(
+            load(format!(
+                "gameoflife-rs board 1\n{}",
+                body.replace("width 3", "width 0")
+            )),
+            BoardError::Invalid(GolError::InvalidSize {
+                width: 0,
+                height: 2
+            })
+        ));
+        assert!(matches!(
+            load(format!(
+                "gameoflife-rs board 1\n{}",
+                body.replace("width 3", "width 4294967295")
+                    .replace("height 2", "height 4294967295")
+            )),
+            BoardError::Invalid(GolError::InvalidSize {
+                width: 4294967295,
+                height: 4294967295
+            })
+        ));
+        assert_eq!(
+            error(&format!(
+                "gameoflife-rs board 1\n{}",
+                body.replace("width 3", "width 8193")
+            )),
+            "a 8193x2 board is too big"
+        );
+    }
+
+    #[test]
+    fn board_snapshot_round_trip() {
+        for &(width, height) in &[(1, 1), (8, 8), (13, 9), (3, 40), (64, 7), (101, 33)] {
+            let mut board = Board::with_size(width, height);
+            board.set_rule("B36/S23".parse().unwrap());
+            board.reset_random(width as u64, 0.4);
+            board.tick_n(5);
+
+            let mut snapshot = Vec::new();
+            board.write_snapshot(&mut snapshot).unwrap();
+            // 4 + 1 + 8 + 1 + 7 + 8 bytes of header, then a bit per cell
+            assert_eq!(snapshot.len(), 29 + (width * height).div_ceil(8));
+
+            let loaded = Board::read_snapshot(&snapshot[..]).unwrap();
+            assert_eq!(loaded, board, "{}x{}", width, height);
+            assert_eq!(loaded.rule(), board.rule());
+            assert_eq!(loaded.generation(), 5);
+            assert_eq!(loaded.zobrist_hash(), board.zobrist_hash());
+            assert_eq!(loaded.population(), board.population());
+        }
+    }
+
+    #[test]
+    fn board_snapshot_errors() {
+        let mut board = Board::with_size(13, 9);
+        board.reset_random(2, 0.5);
+        let mut snapshot = Vec::new();
+        board.write_snapshot(&mut snapshot).unwrap();
+        let error = |bytes: &[u8]| Board::read_snapshot(bytes).unwrap_err().to_string();
+
+        // However much is chopped off the end, it's an error, never a panic
+        for length in 0..snapshot.len() {
+            let error = Board::read_snapshot(&snapshot[..length]).unwrap_err();
+            assert!(matches!(error, BoardError::Corrupt(_)), "{:?}", error);
+        }
+        assert_eq!(error(&snapshot[..3]), "snapshot ends before its header");
+        assert_eq!(error(&snapshot[..10]), "snapshot ends before its size");
+        assert_eq!(error(&snapshot[..17]), "snapshot ends before its rule");
+        assert_eq!(
+            error(&snapshot[..25]),
+            "snapshot ends before its generation"
+        );
+        assert_eq!(
+            error(&snapshot[..40]),
+            "snapshot ends after 12 of its 15 bytes of cells"
+        );
+        let mut longer = snapshot.clone();
+        longer.push(0);
+        assert_eq!(error(&longer), "snapshot has data after its cells");
+
+        let mut corrupted = snapshot.clone();
+        corrupted[0] = b'X';
+        assert_eq!(
+            error(&corrupted),
+            "not a board snapshot: starts [88, 79, 76, 66]"
+        );
+        corrupted = snapshot.clone();
+        corrupted[4] = 2;
+        assert!(matches!(
+            Board::read_snapshot(&corrupted[..]),
+            Err(BoardError::UnsupportedVersion {
+                found: 2,
+                supported: 1
+            })
+        ));
+        assert_eq!(
+            error(&corrupted),
+            "saved in format version 2, but only version 1 can be read"
+        );
+        corrupted = snapshot.clone();
+        corrupted[5..9].copy_from_slice(&0u32.to_le_bytes());
+        assert!(matches!(
+            Board::read_snapshot(&corrupted[..]),
+            Err(BoardError::Invalid(GolError::InvalidSize {
+                width: 0,
+                height: 9
+            }))
+        ));
+        corrupted = snapshot.clone();
+        corrupted[5..13].copy_from_slice(&[0xff; 8]);
+        assert!(error(&corrupted).starts_with("snapshot ends after"));
+        corrupted = snapshot.clone();
+        corrupted[15] = b'X';
+        assert!(matches!(
+            Board::read_snapshot(&corrupted[..]),
+            Err(BoardError::Invalid(GolError::InvalidRule(_)))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn board_json_round_trip() {
+        let path = std::env::temp_dir().join("gameoflife-rs-board_json_round_trip.json");
+        let mut board = Board::with_size(21, 14);
+        board.set_rule("B36/S23".parse().unwrap());
+        board.set_border_mode(BorderMode::Reflective);
+        board.reset_random(8, 0.3);
+        board.tick_n(9);
+
+        board.save_json(&path).unwrap();
+        let loaded = Board::load_json(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.generation(), 9);
+        assert_eq!(loaded.rule(), board.rule());
+        assert_eq!(loaded.border_mode(), BorderMode::Reflective);
+        assert!(!loaded.wrap());
+
+        let error = Board::load_json(&path).unwrap_err();
+        assert!(matches!(&error, BoardError::Io(e) if e.kind() == io::ErrorKind::NotFound));
+        std::fs::write(&path, "{\"width\":3").unwrap();
+        let error = Board::load_json(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(error, BoardError::Corrupt(_)), "{:?}", error);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn board_json_fixture() {
+        // Written by the first version with JSON support, and must stay
+        // readable
+        let board: Board =
+            serde_json::from_str(include_str!("../tests/fixtures/board-v1.json")).unwrap();
+        assert_eq!(
+            board,
+            Board::from_rows(&[".#...", "..#..", "###..", "....."]).unwrap()
+        );
+        assert_eq!(board.rule(), "B36/S23".parse().unwrap());
+        assert!(board.wrap());
+        assert_eq!(board.border_mode(), BorderMode::Dead);
+        assert_eq!(board.generation(), 12);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn board_json_validates() {
+        let error = |json: &str| serde_json::from_str::<Board>(json).unwrap_err().to_string();
+        let json = |width, alive| {
+            format!(
+                r#"{{"width":{},"height":2,"rule":"B3/S23","wrap":false,"border_mode":"Dead","generation":0,"alive":{}}}"#,
+                width, alive
+            )
+        };
+        assert!(error(&json(3, "[[4,1]]"))
+            .starts_with("(4, 1) is off a 3x2 board, whose cells run from (1, 1) to (3, 2)"));
+        assert!(error(&json(0, "[]")).starts_with("a 0x2 board has no cells"));
+        assert!(error(&json(usize::MAX, "[]"))
+            .starts_with(&format!("a {}x2 board is too big", usize::MAX)));
+        assert!(error(&json(3, "[[1]]")).contains("invalid length 1"));
+        assert!(error(r#"{"width":3}"#).contains("missing field"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn board_ron_round_trip() {
+        let mut board = Board::from_rows(&[".......", ".###.#.", ".......", "##...##"]).unwrap();
+        board.set_rule("B36/S23".parse().unwrap());
+        board.tick();
+        let path = std::env::temp_dir().join("gameoflife-rs-board_ron_round_trip.ron");
+        board.save_ron(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("RonBoard("), "{}", text);
+        assert!(text.contains("rule: \"B36/S23\""), "{}", text);
+
+        let loaded = Board::load_ron(&path).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.generation(), 1);
+        assert_eq!(loaded.rule(), board.rule());
+        assert_eq!(loaded.zobrist_hash(), board.zobrist_hash());
+
+        // Hand-edited runs off the board are rejected
+        std::fs::write(&path, text.replacen("length: ", "length: 10", 1)).unwrap();
+        let error = Board::load_ron(&path).unwrap_err();
+        assert!(matches!(error, BoardError::Corrupt(_)), "{:?}", error);
+        std::fs::write(&path, text.replace("B36/S23", "B9/S23")).unwrap();
+        let error = Board::load_ron(&path).unwrap_err();
+        assert!(matches!(
+            error,
+            BoardError::Invalid(GolError::InvalidRule(_))
+        ));
+        for &(from, to) in &[
+            ("width: 7", "width: 0"),
+            ("width: 7", "width: 18446744073709551615"),
+            ("height: 4", "height: 4294967295"),
+        ] {
+            std::fs::write(&path, text.replace(from, to)).unwrap();
+            let error = Board::load_ron(&path).unwrap_err();
+            assert!(
+                matches!(error, BoardError::Invalid(GolError::InvalidSize { .. })),
+                "{:?}",
+                error
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+        let error = Board::load_ron(&path).unwrap_err();
+        assert!(matches!(&error, BoardError::Io(e) if e.kind() == io::ErrorKind::NotFound));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn board_from_image() {
+        use image::{GrayAlphaImage, GrayImage, LumaA};
+
+        // A glider in black on white, with grey either side of the
+        // cut-off
+        let path = std::env::temp_dir().join("gameoflife-rs-board_from_image.png");
+        let mut image = GrayImage::from_pixel(5, 4, image::Luma([255]));
+        for &(x, y) in &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            image.put_pixel(x, y, image::Luma([0]));
+        }
+        image.put_pixel(4, 0, image::Luma([127]));
+        image.put_pixel(4, 1, image::Luma([128]));
+        image.save(&path).unwrap();
+        let board = Board::from_image(&path).unwrap();
+        assert_eq!(
+            board,
+            Board::from_rows(&[".#..#", "..#..", "###..", "....."]).unwrap()
+        );
+
+        // Clear pixels are dead, even black ones
+        let mut image = GrayAlphaImage::from_pixel(2, 1, LumaA([0, 0]));
+        image.put_pixel(1, 0, LumaA([0, 255]));
+        image.save(&path).unwrap();
+        let board = Board::from_image(&path).unwrap();
+        assert_eq!(board.live_cells().collect::<Vec<_>>(), vec![(2, 1)]);
+
+        std::fs::write(&path, "not an image").unwrap();
+        assert!(matches!(
+            Board::from_image(&path),
+            Err(ImageLoadError::Image(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn board_life106_errors() {
+        let error = |input| match Board::from_life106(input) {
+            Err(GolError::Parse { line, .. }) => line,
+            Err(e) => panic!("{:?} gave {}", input, e),
+            Ok(_) => panic!("{:?} parsed", input),
+        };
+        assert_eq!(error(""), 1);
+        assert_eq!(error("0 0\n"), 1);
+        assert_eq!(error("#Life 1.05\n0 0\n"), 1);
+        assert_eq!(error("#Life 1.06\n0 0\n1\n"), 3);
+        assert_eq!(error("#Life 1.06\n0 0 0\n"), 2);
+        assert_eq!(error("#Life 1.06\nx 0\n"), 2);
+        assert_eq!(
+            error("#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n"),
+            2
+        );
+        assert_eq!(error("#Life 1.06\n9223372036854775807 0\n"), 2);
+
+        // Cells too far apart to fit on a board
+        for input in [
+            "#Life 1.06\n-9223372036854775000 0\n9223372036854775000 0\n",
+            "#Life 1.06\n0 0\n1099511627776 0\n",
+            "#Life 1.06\n0 0\n0 8192\n",
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                Board::from_life106(input),
+                Err(GolError::InvalidSize { .. })
+            ));
+        }
+        let widest = Board::from_life106(&format!(
+            "#Life 1.06\n0 0\n{} 0\n",
+            MAX_LOADED_SIZE - 2 * PATTERN_MARGIN - 1
+        ))
+        .unwrap();
+        assert_eq!(widest.width(), MAX_LOADED_SIZE);
+
+        let empty = Board::from_life106("#Life 1.06\n").unwrap();
+        assert_eq!(empty.population(), 0);
+        assert_eq!(empty.to_life106(), "#Life 1.06\n");
+    }
+
+    #[test]
+    fn board_center_pattern() {
+        // Glider against the top-left corner of a 10x11 board
+        let mut board = Board::with_size(10, 11);
+        for coords in [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        board.center_pattern().unwrap();
+
+        // The spare column goes on the right
+        let mut expected = Board::with_size(10, 11);
+        for coords in [(5, 5), (6, 6), (4, 7), (5, 7), (6, 7)].iter() {
+            expected.get_cell_mut(*coords).unwrap().flip();
+        }
+        assert_eq!(board, expected);
+
+        // Already centred, so nothing moves
+        board.center_pattern().unwrap();
+        assert_eq!(board, expected);
+
+        let mut empty = Board::with_size(10, 11);
+        empty.center_pattern().unwrap();
+        assert_eq!(empty.population(), 0);
+    }
+
+    #[test]
+    fn board_wrap_glider() {
+        // A glider on a wrapped 8x8 board crosses every edge and is back
+        // where it started after 32 generations
+        let mut board = Board::with_size(8, 8);
+        board.set_wrap(true);
+        for coords in [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        let start = board.clone();
+
+        for generation in 1..=32 {
+            board.tick();
+            assert_eq!(board.population(), 5, "generation {}", generation);
+        }
+        assert_eq!(board, start);
+
+        // Without wrapping it turns into a block in the corner
+        board.set_wrap(false);
+        for _ in 0..32 {
+            board.tick();
+        }
+        assert_eq!(board.population(), 4);
+    }
+
+    #[test]
+    fn board_rule() {
+        // Under Replicator (B1357/S1357) a single cell becomes a ring of eight
+        let mut board = Board::with_size(5, 5);
+        board.set_rule("B1357/S1357".parse().unwrap());
+        board.get_cell_mut((3, 3)).unwrap().flip();
+        board.tick();
+        for x in 2..=4 {
+            for y in 2..=4 {
+                assert_eq!(board.get_cell((x, y)).unwrap().alive, (x, y) != (3, 3));
+            }
+        }
+        assert_eq!(board.population(), 8);
+    }
+
+    #[test]
+    fn board_run_collecting_stats() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        // Blinker, which keeps the same population as it turns
+        for coords in [(4, 3), (4, 4), (4, 5)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        assert_eq!(board.run_collecting_stats(4), vec![3; 4]);
+        assert_eq!(board.generation(), 4);
+
+        // Beacon, whose inner corners blink on and off
+        let mut board = Board::with_size(8, 8);
+        for coords in [(2, 2), (3, 2), (2, 3), (5, 4), (4, 5), (5, 5)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        assert_eq!(board.run_collecting_stats(4), vec![8, 6, 8, 6]);
+        assert!(board.run_collecting_stats(0).is_empty());
+    }
+
+    #[test]
+    fn board_render_heatmap() {
+        let mut board = Board::with_size(3, 2);
+        let mut heat = HeatMap::new(3, 2);
+        board.get_cell_mut((1, 1)).unwrap().flip();
+        heat.record(&board);
+        assert_eq!(board.render_heatmap(&heat), "█░░\n░░░\n");
+    }
+
+    #[test]
+    fn board_render_scaled() {
+        // 7x5 in blocks of 3 is 3x2, with partial blocks at the right
+        // and bottom
+        let mut board =
+            Board::from_rows(&[".......", ".....#.", ".......", ".......", "......#"]).unwrap();
+        board.set_glyphs('#', '.');
+        assert_eq!(board.render_scaled(3, 2), ".#.\n..#\n");
+        assert_eq!(board.render_scaled(4, 3), "..#.\n....\n...#\n");
+        assert_eq!(board.render_scaled(7, 5), board.to_string());
+        assert_eq!(board.render_scaled(100, 100), board.to_string());
+        assert_eq!(board.render_scaled(1, 1), "#\n");
+        assert_eq!(board.render_scaled(0, 5), "");
+
+        board.clear();
+        assert_eq!(board.render_scaled(1, 1), ".\n");
+    }
+
+    #[test]
+    fn board_render_braille() {
+        // Each cell of a 2x4 block alone, then the whole block
+        let dots = ['⠁', '⠈', '⠂', '⠐', '⠄', '⠠', '⡀', '⢀'];
+        for (i, &dot) in dots.iter().enumerate() {
+            let mut board = Board::with_size(2, 4);
+            board.set_alive((i % 2 + 1, i / 2 + 1), true).unwrap();
+            assert_eq!(board.render_braille(), format!("{}\n", dot));
+        }
+        let mut board = Board::with_size(2, 4);
+        board.fill();
+        assert_eq!(board.render_braille(), "⣿\n");
+
+        // A glider in a 5x5 board takes 3x2 characters, the last
+        // column and row only partly on the board
+        let glider = Board::from_rows(&[".#...", "..#..", "###..", ".....", "....."]).unwrap();
+        assert_eq!(glider.render_braille(), "⠬⠆⠀\n⠀⠀⠀\n");
+    }
+
+    #[test]
+    fn board_center_of_mass() {
+        let (_x_coord, _y_coord, mut board, _rng) = setup();
+        assert_eq!(board.center_of_mass(), None);
+
+        for coords in [(2, 1), (3, 2), (1, 3), (2, 3), (3, 3)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        assert_eq!(board.center_of_mass(), Some((2.2, 2.4)));
+
+        // A glider moves one cell down and to the right every four generations
+        let prev = board.clone();
+        for _ in 0..4 {
+            board.tick();
+        }
+        assert_eq!(board.center_of_mass_velocity(&prev), (1.0, 1.0));
+        assert_eq!(Board::new().center_of_mass_velocity(&prev), (0.0, 0.0));
+    }
+
+    #[test]
+    fn board_custom_transition() {
+        // Cells with exactly four alive neighbours flip, and every
+        // other cell keeps its state
+        let mut board = Board::with_size(5, 5);
+        board.set_transition(|alive, num_adjacent_alive| alive != (num_adjacent_alive == 4));
+        for coords in [(3, 2), (2, 3), (4, 3), (3, 4)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+
+        // The middle of the plus is the only cell with four neighbours
+        assert_eq!(board.get_cells_to_flip(), vec![(3, 3)]);
+        board.tick();
+        assert!(board.get_cell((3, 3)).unwrap().alive);
+        assert_eq!(board.population(), 5);
+        board.tick();
+        assert_eq!(board.population(), 4);
+
+        // Setting a rule replaces the transition. Under Conway's
+        // rule the four cells are a tub, which never changes.
+        board.set_rule(Rule::CONWAY);
+        let tub = board.clone();
+        board.tick();
+        assert_eq!(board, tub);
+    }
+
+    #[test]
+    fn board_von_neumann_count() {
+        // A full 3x3 block: the middle cell has 8 Moore neighbours
+        // but only 4 Von Neumann ones, and a corner 3 and 2.
+        let mut board = Board::with_size(5, 5);
+        for x in 2..=4 {
+            for y in 2..=4 {
+                board.get_cell_mut((x, y)).unwrap().flip();
+            }
+        }
+        assert_eq!(board.neighborhood(), Neighborhood::Moore);
+        assert_eq!(board.count_adjacent_alive((3, 3)), 8);
+        assert_eq!(board.count_adjacent_alive((2, 2)), 3);
+
+        board.set_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(board.count_adjacent_alive((3, 3)), 4);
+        assert_eq!(board.count_adjacent_alive((2, 2)), 2);
+        assert_eq!(board.count_adjacent_alive((1, 3)), 1);
+        assert_eq!(board.count_adjacent_alive((1, 1)), 0);
+
+        // Diagonal cells aren't neighbours, so under Conway's rule a
+        // diagonal line dies out in one generation instead of
+        // shrinking to a single cell
+        let mut board = Board::with_size(5, 5);
+        board.set_neighborhood(Neighborhood::VonNeumann);
+        for i in 2..=4 {
+            board.get_cell_mut((i, i)).unwrap().flip();
+        }
+        board.tick();
+        assert_eq!(board.population(), 0);
+    }
+
+    #[test]
+    fn board_von_neumann_replicator() {
+        // Fredkin's replicator: under B13/S13 with the Von Neumann
+        // neighbourhood each cell becomes the parity of its four
+        // neighbours, and after 2^k generations any pattern has turned
+        // into four copies of itself, 2^k cells away along each axis.
+        let mut board = Board::with_size(20, 20);
+        board.set_rule("B13/S13".parse().unwrap());
+        board.set_neighborhood(Neighborhood::VonNeumann);
+        let l_tromino = [(0, 0), (1, 0), (0, 1)];
+        for &(dx, dy) in l_tromino.iter() {
+            board.get_cell_mut((10 + dx, 10 + dy)).unwrap().flip();
+        }
+
+        for _ in 0..4 {
+            board.tick();
+        }
+
+        let mut expected = Board::with_size(20, 20);
+        for &(sx, sy) in [(6, 10), (14, 10), (10, 6), (10, 14)].iter() {
+            for &(dx, dy) in l_tromino.iter() {
+                expected.get_cell_mut((sx + dx, sy + dy)).unwrap().flip();
+            }
+        }
+        assert_eq!(board, expected);
+        assert_eq!(board.population(), 12);
+    }
+
+    #[test]
+    fn board_b0_background() {
+        // Under B0/S8 every cell with no alive neighbours is born, and
+        // only cells with all eight alive survive
+        let mut board = Board::with_size(10, 10);
+        board.set_rule("B0/S8".parse().unwrap());
+        board.set_alive_at((5, 5), true);
+
+        // Everything except the cell and its neighbours comes alive,
+        // including the plane beyond the board
+        board.tick();
+        assert!(board.background());
+        assert_eq!(board.population(), 100 - 9);
+        assert!(board.is_alive_at((0, 0)) && board.is_alive_at((20, 20)));
+        assert!(!board.is_alive_at((4, 4)) && !board.is_alive_at((6, 6)));
+
+        // Cells around the hole die, the middle of the hole is born
+        // again, and the edges survive since the background is alive
+        board.tick();
+        assert!(board.background());
+        assert_eq!(board.population(), 100 - 25 + 1);
+        assert!(board.is_alive_at((5, 5)));
+        assert!(!board.is_alive_at((3, 3)) && !board.is_alive_at((7, 7)));
+        assert!(board.is_alive_at((1, 1)) && board.is_alive_at((10, 5)));
+        assert_eq!(board.get_cells_to_flip(), board.cells_to_flip(None));
+        assert_eq!(board.zobrist, Some(board.compute_zobrist()));
+
+        // Undo brings back the background too
+        board.undo();
+        board.undo();
+        assert!(!board.background());
+        assert_eq!(board.population(), 1);
+
+        // An empty board fills and stays full, and a growing board
+        // fills its new cells
+        let mut board = Board::with_size(10, 10);
+        board.set_rule("B0/S8".parse().unwrap());
+        board.set_grow(Some((40, 40)));
+        board.set_alive_at((2, 2), true);
+        for _ in 0..5 {
+            board.tick();
+        }
+        assert!(board.width() > 10);
+        assert!(board.get_cell((1, 1)).unwrap().alive);
+        let dead = board.width() * board.height() - board.population();
+        assert!(board.background() && dead > 0);
+
+        // Seeds dies back every generation, on the board and off it
+        let mut board = Board::with_size(10, 10);
+        board.set_rule("B0/S".parse().unwrap());
+        for generation in 1..=4 {
+            board.tick();
+            let alive = generation % 2 == 1;
+            assert_eq!(board.background(), alive);
+            assert_eq!(board.population(), if alive { 100 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn board_grid_round_trip() {
+        let mut board = Board::with_size(4, 3);
+        for coords in [(1, 1), (4, 1), (2, 3)].iter() {
+            board.set_alive_at(*coords, true);
+        }
+        let grid = board.to_grid();
+        assert_eq!(
+            grid,
+            vec![
+                vec![true, false, false, true],
+                vec![false; 4],
+                vec![false, true, false, false],
+            ]
+        );
+        let loaded = Board::from_grid(&grid).unwrap();
+        assert_eq!(loaded, board);
+        assert_eq!(loaded.zobrist_hash(), board.zobrist_hash());
+
+        assert_eq!(
+            Board::from_grid(&[vec![true, false], vec![true]]).err(),
+            Some(GolError::RaggedGrid {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        for empty in [vec![], vec![vec![]]].iter() {
+            assert!(matches!(
+                Board::from_grid(empty),
+                Err(GolError::InvalidSize { width: 0, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn board_b0_alternating() {
+        // Under B0/S nothing survives, so the background flips every
+        // generation, and a lone cell flips with it into a dead hole
+        // in a live plane and back again
+        let mut board = Board::with_size(7, 7);
+        board.set_rule("B0/S".parse().unwrap());
+        board.set_alive_at((4, 4), true);
+        let start = board.clone();
+
+        board.tick();
+        assert!(board.background());
+        // Rendering past the edges shows the live background too
+        let hole = "\
+███████████
+███████████
+███████████
+███████████
+████░░░████
+████░░░████
+████░░░████
+███████████
+███████████
+";
+        assert_eq!(life::render(&board, (-1, -1), 11, 9), hole);
+
+        // The middle of the hole has no alive neighbours, so is born,
+        // and everything else dies
+        board.tick();
+        assert!(!board.background());
+        assert_eq!(board, start);
+    }
+
+    #[test]
+    fn board_zobrist_incremental() {
+        // After each kind of change the kept hash matches one
+        // computed from scratch
+        fn check(board: &Board) {
+            assert_eq!(board.zobrist, Some(board.compute_zobrist()));
+        }
+
+        let mut board = Board::with_size(30, 30);
+        check(&board);
+        assert_eq!(board.zobrist_hash(), 0);
+        for &coords in [(5, 5), (6, 5), (7, 5), (7, 4), (6, 3)].iter() {
+            board.set_alive_at(coords, true);
+            check(&board);
+        }
+        board.flip_at((5, 5));
+        board.flip_at((20, 20));
+        board.set_alive_at((6, 5), true);
+        check(&board);
+
+        for _ in 0..20 {
+            board.tick();
+            check(&board);
+        }
+        board.center_pattern().unwrap();
+        check(&board);
+        let glider = board.copy_region(1, 1, 15, 15);
+        check(&glider);
+        board.paste_region(&glider, (16, 16));
+        check(&board);
+        board.undo();
+        check(&board);
+        board.reset_random(3, 0.4);
+        check(&board);
+
+        // Edits through get_cell_mut are caught up with by the next tick
+        board.get_cell_mut((1, 1)).unwrap().flip();
+        assert_eq!(board.zobrist, None);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+        board.tick();
+        check(&board);
+
+        // Growing moves every cell, and the parallel paths add up
+        // the changes from each strip
+        let mut board = Board::with_size(10, 10);
+        board.set_grow(Some((60, 60)));
+        board.set_parallelism(Parallelism::Strips(Some(3)));
+        board.reset_random(5, 0.5);
+        for _ in 0..10 {
+            board.tick();
+            check(&board);
+        }
+        #[cfg(feature = "parallel")]
+        {
+            board.set_parallelism(Parallelism::Rayon);
+            for _ in 0..10 {
+                board.tick();
+                check(&board);
+            }
+        }
+    }
+
+    #[test]
+    fn board_zobrist_equal_boards() {
+        // Boards with the same cells hash the same, however they got there
+        let mut ticked = Board::with_size(20, 20);
+        ticked.reset_random(11, 0.35);
+        for _ in 0..5 {
+            ticked.tick();
+        }
+
+        let mut copied = Board::with_size(20, 20);
+        for x in (1..=20).rev() {
+            for y in 1..=20 {
+                if ticked.get_cell((x, y)).unwrap().alive {
+                    copied.set_alive_at((x as i64, y as i64), true);
+                }
+            }
+        }
+        let mut edited = Board::with_size(20, 20);
+        for x in 1..=20 {
+            for y in 1..=20 {
+                edited.get_cell_mut((x, y)).unwrap().alive = ticked.get_cell((x, y)).unwrap().alive;
+            }
+        }
+
+        assert!(ticked == copied && ticked == edited);
+        assert_ne!(ticked.zobrist_hash(), 0);
+        assert_eq!(ticked.zobrist_hash(), copied.zobrist_hash());
+        assert_eq!(ticked.zobrist_hash(), edited.zobrist_hash());
+
+        // and different cells almost certainly hash differently
+        copied.flip_at((10, 10));
+        assert_ne!(ticked.zobrist_hash(), copied.zobrist_hash());
+    }
+
+    #[test]
+    fn board_set_alive() {
+        let mut board = Board::with_size(4, 3);
+        board.set_alive((4, 3), true).unwrap();
+        board.set_alive((1, 1), true).unwrap();
+        board.set_alive((1, 1), false).unwrap();
+        assert_eq!(board.live_cells().collect::<Vec<_>>(), vec![(4, 3)]);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+
+        for &coords in [(0, 1), (1, 0), (5, 1), (1, 4)].iter() {
+            let err = board.set_alive(coords, true).unwrap_err();
+            assert!(matches!(
+                err,
+                BoardError::OutOfBounds(e) if e == OutOfBoundsError {
+                    coords,
+                    board: (4, 3)
+                }
+            ));
+        }
+        assert_eq!(
+            board.set_alive((5, 1), true).unwrap_err().to_string(),
+            "(5, 1) is off a 4x3 board, whose cells run from (1, 1) to (4, 3)"
+        );
+        assert_eq!(board.population(), 1);
+    }
+
+    #[test]
+    fn board_live_cells() {
+        assert_eq!(Board::new().live_cells().next(), None);
+
+        let board = Board::from_ascii("#..#\n.#..\n...#", '#').unwrap();
+        let cells: Vec<_> = board.live_cells().collect();
+        assert_eq!(cells, vec![(1, 1), (4, 1), (2, 2), (4, 3)]);
+
+        let mut board = Board::with_size(30, 20);
+        board.reset_random(4, 0.5);
+        assert_eq!(board.live_cells().count(), board.population());
+        assert!(board
+            .live_cells()
+            .all(|coords| board.get_cell(coords).is_some_and(|cell| cell.alive)));
+    }
+
+    #[test]
+    fn board_iter() {
+        let mut board = Board::with_size(7, 5);
+        board.reset_random(6, 0.5);
+        assert_eq!(board.iter().count(), 7 * 5);
+        assert!(board.iter().all(|(coords, alive)| board
+            .get_cell(coords)
+            .is_some_and(|cell| cell.alive == alive)));
+        let alive = board.iter().filter(|&(_, alive)| alive).count();
+        assert_eq!(alive, board.population());
+
+        // A checkerboard, counted against its own coordinates
+        let mut visited = Vec::new();
+        board.for_each_mut(|(x, y), _| {
+            visited.push((x, y));
+            (x + y) % 2 == 0
+        });
+        assert!(visited
+            .iter()
+            .copied()
+            .eq(board.iter().map(|(coords, _)| coords)));
+        assert_eq!(board.population(), 18);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+        assert!(board
+            .iter()
+            .all(|((x, y), alive)| alive == ((x + y) % 2 == 0)));
+    }
+
+    #[test]
+    fn board_diff() {
+        let mut board = Board::from_ascii(".....\n.....\n.###.\n.....\n.....", '#').unwrap();
+        assert!(board.diff(&board).unwrap().is_empty());
+
+        let before = board.clone();
+        board.tick();
+        let diff = before.diff(&board).unwrap();
+        assert_eq!(diff.births, vec![(3, 2), (3, 4)]);
+        assert_eq!(diff.deaths, vec![(2, 3), (4, 3)]);
+        let back = board.diff(&before).unwrap();
+        assert_eq!((&back.births, &back.deaths), (&diff.deaths, &diff.births));
+
+        // Replaying the diff catches up without ticking
+        assert_eq!(BoardDiff::compute(&before, &board).unwrap(), diff);
+        let mut replayed = before.clone();
+        diff.apply(&mut replayed).unwrap();
+        assert_eq!(replayed, board);
+        assert_eq!(replayed.generation(), 0);
+        assert_eq!(replayed.zobrist_hash(), board.zobrist_hash());
+        let mut small = Board::with_size(3, 3);
+        assert!(matches!(
+            diff.apply(&mut small),
+            Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords: (3, 4),
+                board: (3, 3)
+            }))
+        ));
+        assert_eq!(small.population(), 0);
+
+        let err = board.diff(&Board::with_size(5, 6)).unwrap_err();
+        assert!(matches!(
+            err,
+            BoardError::DimensionMismatch(SizeMismatchError { right: (5, 6), .. })
+        ));
+        assert_eq!(err.to_string(), "can't compare a 5x5 board with a 5x6 one");
+    }
+
+    #[test]
+    fn board_count_clusters() {
+        assert_eq!(Board::with_size(5, 5).count_clusters(), 0);
+
+        // Two blinkers far apart, and an R-pentomino
+        let blinkers =
+            Board::from_rows(&["..........", ".###......", "..........", "......###."]).unwrap();
+        assert_eq!(blinkers.count_clusters(), 2);
+        let pentomino = Board::from_rows(&[".##", "##.", ".#."]).unwrap();
+        assert_eq!(pentomino.count_clusters(), 1);
+
+        // Cells touching only at a corner are one cluster, and a gap
+        // of one dead cell makes two
+        let layout =
+            Board::from_rows(&["#.....#", ".#....#", "..#....", ".....##", "#.#...."]).unwrap();
+        assert_eq!(layout.count_clusters(), 5);
+        assert_eq!(layout.count_clusters(), layout.find_islands().len());
+    }
+
+    #[test]
+    fn board_flood_fill() {
+        let mut board = Board::from_ascii("......\n.##...\n..#..#\n......", '#').unwrap();
+        // The L grows a ring, which doesn't reach the lone cell
+        assert_eq!(board.flood_fill_alive((2, 2)).unwrap(), 12);
+        assert_eq!(board.to_string(), "████░░\n████░░\n████░█\n░███░░\n");
+        assert_eq!(board.population(), 16);
+
+        assert_eq!(board.flood_fill_dead((1, 1)).unwrap(), 15);
+        assert_eq!(board.flood_fill_dead((1, 1)).unwrap(), 0);
+        assert_eq!(board.live_cells().collect::<Vec<_>>(), vec![(6, 3)]);
+
+        // A dead origin comes to life first
+        assert_eq!(board.flood_fill_alive((6, 1)).unwrap(), 4);
+        assert_eq!(board.population(), 5);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+
+        let err = OutOfBoundsError {
+            coords: (7, 1),
+            board: (6, 4),
+        };
+        assert!(
+            matches!(board.flood_fill_alive((7, 1)), Err(BoardError::OutOfBounds(e)) if e == err)
+        );
+        assert!(
+            matches!(board.flood_fill_dead((7, 1)), Err(BoardError::OutOfBounds(e)) if e == err)
+        );
+        assert_eq!(board.population(), 5);
+    }
+
+    #[test]
+    fn board_snapshot_restore() {
+        let mut board = Board::with_size(30, 30);
+        board.reset_random(8, 0.4);
+        board.tick_n(5);
+        let fork = board.snapshot();
+        assert_eq!((fork.generation(), fork.size()), (5, (30, 30)));
+        assert_eq!(board.snapshot(), fork);
+
+        board.tick_n(10);
+        let conway = board.snapshot();
+        assert_ne!(conway, fork);
+
+        // A different rule from the same point
+        board.restore(&fork);
+        assert_eq!(board.generation(), 5);
+        assert_eq!(board.snapshot(), fork);
+        board.set_rule(Rule::new(&[3, 6], &[2, 3]).unwrap());
+        board.tick_n(10);
+        assert_ne!(board.snapshot(), conway);
+
+        // Restoring again, and across a change of size
+        board.set_rule(Rule::CONWAY);
+        board.restore(&fork);
+        board.tick_n(10);
+        assert_eq!(board.snapshot(), conway);
+        let mut other = Board::with_size(3, 3);
+        other.restore(&conway);
+        assert_eq!(other, board);
+        assert_eq!(other.population(), board.population());
+    }
+
+    #[test]
+    fn board_border_modes() {
+        let mut board = Board::with_size(5, 5);
+        assert_eq!(board.border_mode(), BorderMode::Dead);
+        board.tick();
+        assert!(board.is_dead());
+
+        // Every edge cell but the corners has three alive neighbours
+        board.set_border_mode(BorderMode::Alive);
+        assert!(!board.is_dead());
+        board.set_detect_edges(true);
+        board.tick();
+        assert_eq!(board.population(), 12);
+        assert_eq!(board.to_string(), "░███░\n█░░░█\n█░░░█\n█░░░█\n░███░\n");
+        assert!(board.take_edge_events().is_empty());
+        assert_eq!(board.get_cells_to_flip(), board.cells_to_flip(None));
+
+        // Cut along its lines of symmetry, a wrapped board made of four
+        // mirrored copies behaves like a single copy with mirror edges
+        let (width, height) = (9, 7);
+        let mut reflective = Board::with_size(width, height);
+        reflective.reset_random(5, 0.4);
+        reflective.set_border_mode(BorderMode::Reflective);
+        let mut mirrored = Board::with_size(2 * width, 2 * height);
+        mirrored.set_wrap(true);
+        for (x, y) in reflective.live_cells() {
+            for &mx in [x, 2 * width + 1 - x].iter() {
+                for &my in [y, 2 * height + 1 - y].iter() {
+                    mirrored.set_alive((mx, my), true).unwrap();
+                }
+            }
+        }
+        for _ in 0..30 {
+            assert_eq!(
+                reflective.get_cells_to_flip(),
+                reflective.cells_to_flip(None)
+            );
+            reflective.tick();
+            mirrored.tick();
+            assert!(reflective.iter().all(|(coords, alive)| mirrored
+                .get_cell(coords)
+                .is_some_and(|cell| cell.alive == alive)));
+        }
+        assert!(reflective.population() > 0);
+    }
+
+    #[test]
+    fn board_from_str() {
+        let mut board = Board::with_size(17, 9);
+        board.reset_random(11, 0.5);
+        let parsed: Board = board.to_string().parse().unwrap();
+        assert_eq!(parsed, board);
+
+        let blinker: Board = ".....\n..#..\n..#..\n..#..\n.....".parse().unwrap();
+        assert_eq!(
+            blinker.live_cells().collect::<Vec<_>>(),
+            vec![(3, 2), (3, 3), (3, 4)]
+        );
+        // Either glyphs, Windows line endings and trailing newlines
+        assert_eq!(
+            "░░░░░\r\n░░█░░\r\n░░#░░\r\n..█..\r\n.....\r\n\r\n".parse(),
+            Ok(blinker)
+        );
+
+        assert_eq!(
+            "...\n..\n...".parse::<Board>(),
+            Err(GolError::Parse {
+                line: 2,
+                message: String::from("2 cells, but the first line has 3")
+            })
+        );
+        assert_eq!(
+            "...\n.o.".parse::<Board>(),
+            Err(GolError::Parse {
+                line: 2,
+                message: String::from("unexpected character 'o'")
+            })
+        );
+        assert!("".parse::<Board>().is_err());
+    }
+
+    #[test]
+    fn board_from_rows() {
+        let board = Board::from_rows(&["....", ".##.", ".##.", "...."]).unwrap();
+        assert_eq!((board.width(), board.height()), (4, 4));
+        assert_eq!(
+            board.live_cells().collect::<Vec<_>>(),
+            vec![(2, 2), (3, 2), (2, 3), (3, 3)]
+        );
+
+        assert_eq!(
+            Board::from_rows(&["....", ".##", "...."]),
+            Err(GolError::RaggedGrid {
+                row: 1,
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Board::from_rows(&["....", ".## "]),
+            Err(GolError::Parse {
+                line: 2,
+                message: String::from("' ' is neither '#' nor '.'")
+            })
+        );
+        assert!(Board::from_rows(&[]).is_err());
+    }
+
+    #[test]
+    fn board_neighbors() {
+        let mut board = Board::with_size(5, 4);
+        let neighbors = |board: &Board, coords| {
+            let mut neighbors: Vec<_> = board.neighbors(coords).collect();
+            neighbors.sort_unstable();
+            neighbors
+        };
+        assert_eq!(
+            neighbors(&board, (2, 2)),
+            vec![
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 1),
+                (2, 3),
+                (3, 1),
+                (3, 2),
+                (3, 3)
+            ]
+        );
+        assert_eq!(neighbors(&board, (5, 4)), vec![(4, 3), (4, 4), (5, 3)]);
+
+        board.set_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(neighbors(&board, (5, 4)), vec![(4, 4), (5, 3)]);
+        board.set_wrap(true);
+        assert_eq!(
+            neighbors(&board, (5, 4)),
+            vec![(1, 4), (4, 4), (5, 1), (5, 3)]
+        );
+
+        // The mirror cells past the corner are the corner and its
+        // neighbours on the edges
+        board.set_wrap(false);
+        board.set_neighborhood(Neighborhood::Moore);
+        board.set_border_mode(BorderMode::Reflective);
+        assert_eq!(
+            neighbors(&board, (1, 1)),
+            vec![
+                (1, 1),
+                (1, 1),
+                (1, 1),
+                (1, 2),
+                (1, 2),
+                (2, 1),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+        board.set_alive((1, 2), true).unwrap();
+        assert_eq!(board.count_adjacent_alive((1, 1)), 2);
+        board.set_border_mode(BorderMode::Alive);
+        assert_eq!(board.count_adjacent_alive((1, 1)), 6);
+    }
+
+    #[test]
+    fn board_get_cell_i() {
+        let mut board = Board::from_rows(&["#..", "...", "..#"]).unwrap();
+        assert_eq!(board.get_cell_i(0, 0).map(|cell| cell.alive), Some(true));
+        assert_eq!(board.get_cell_i(2, 2).map(|cell| cell.alive), Some(true));
+        assert!(board.get_cell_i(1, 2).is_some());
+        assert!(board.get_cell_i(-1, 0).is_none());
+        assert!(board.get_cell_i(0, 3).is_none());
+        assert!(board.get_cell_i(isize::MIN, isize::MAX).is_none());
+
+        // Off to the left is the last column, however far
+        board.set_wrap(true);
+        let last = board.get_cell((3, 1)).unwrap() as *const Cell;
+        assert_eq!(board.get_cell_i(-1, 0).unwrap() as *const Cell, last);
+        assert_eq!(board.get_cell_i(-4, 3).unwrap() as *const Cell, last);
+        assert_eq!(board.get_cell_i(-1, -1).map(|cell| cell.alive), Some(true));
+
+        board.get_cell_i_mut(-1, -1).unwrap().flip();
+        assert_eq!(board.population(), 1);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+    }
+
+    #[test]
+    fn board_index_of() {
+        let board = Board::with_size(4, 3);
+        for (index, (coords, _)) in board.iter().enumerate() {
+            let coord = board.coord_of(index).unwrap();
+            assert_eq!(coord, Coord::new(coords.0 as i64 - 1, coords.1 as i64 - 1));
+            assert_eq!(board.index_of(coord), Some(index));
+        }
+        assert_eq!(board.coord_of(12), None);
+        assert_eq!(board.index_of(Coord::new(-1, 0)), None);
+        assert_eq!(board.index_of(Coord::new(4, 0)), None);
+        assert_eq!(board.index_of(Coord::new(0, 0).offset(0, -1)), None);
+        assert_eq!(board.index_of(Coord::new(i64::MAX, i64::MIN)), None);
+
+        // Wrapping doesn't change the index
+        let mut wrapped = board.clone();
+        wrapped.set_wrap(true);
+        assert_eq!(wrapped.index_of(Coord::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn board_neighbor_counts() {
+        let check = |board: &Board| {
+            let counts = board.neighbor_counts();
+            assert_eq!(counts.len(), board.height());
+            for y in 1..=board.height() {
+                assert_eq!(counts[y - 1].len(), board.width());
+                for x in 1..=board.width() {
+                    assert_eq!(
+                        counts[y - 1][x - 1] as u32,
+                        board.count_adjacent_alive((x, y)),
+                        "({}, {}) on {:?} {:?} wrap {}",
+                        x,
+                        y,
+                        board.neighborhood(),
+                        board.border_mode(),
+                        board.wrap()
+                    );
+                }
+            }
+        };
+
+        for (seed, &(width, height)) in [(1, 1), (2, 1), (1, 5), (2, 2), (3, 3), (17, 9), (40, 31)]
+            .iter()
+            .enumerate()
+        {
+            let mut board = Board::with_size(width, height);
+            board.reset_random(seed as u64, 0.45);
+            for &neighborhood in &[Neighborhood::Moore, Neighborhood::VonNeumann] {
+                board.set_neighborhood(neighborhood);
+                for &border_mode in &[BorderMode::Dead, BorderMode::Alive, BorderMode::Reflective] {
+                    board.set_border_mode(border_mode);
+                    for &wrap in &[false, true] {
+                        board.set_wrap(wrap);
+                        check(&board);
+                    }
+                }
+            }
+        }
+
+        // Past a dead border the cells take the background's state,
+        // alive here after a generation of B0/S8
+        let mut board = Board::with_size(12, 10);
+        board.set_rule("B0/S8".parse().unwrap());
+        board.reset_random(5, 0.3);
+        board.tick();
+        assert!(board.background());
+        check(&board);
+    }
+
+    #[test]
+    fn board_get_neighbors() {
+        // Neighbours in order NW, N, NE, W, E, SW, S, SE, written as '#'
+        // for alive, '.' for dead and '-' for None
+        let states = |states: &str| {
+            let mut neighbors = [None; 8];
+            for (neighbor, c) in neighbors.iter_mut().zip(states.chars()) {
+                *neighbor = match c {
+                    '-' => None,
+                    c => Some(c == '#'),
+                };
+            }
+            neighbors
+        };
+
+        // The top-left corner of a 3x3 board
+        let mut board = Board::from_rows(&["#..", "#.#", "..#"]).unwrap();
+        assert_eq!(board.get_neighbors((1, 1)), states("----.-#."));
+        assert_eq!(board.count_adjacent_alive((1, 1)), 1);
+
+        board.set_border_mode(BorderMode::Alive);
+        assert_eq!(board.get_neighbors((1, 1)), states("####.##."));
+        assert_eq!(board.count_adjacent_alive((1, 1)), 6);
+
+        // Mirrored back onto the corner and the cells beside it
+        board.set_border_mode(BorderMode::Reflective);
+        assert_eq!(board.get_neighbors((1, 1)), states("##.#.##."));
+        assert_eq!(board.count_adjacent_alive((1, 1)), 5);
+
+        // Taken from the far edges
+        board.set_wrap(true);
+        assert_eq!(board.get_neighbors((1, 1)), states("#....##."));
+        assert_eq!(board.count_adjacent_alive((1, 1)), 3);
+
+        board.set_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(board.count_adjacent_alive((1, 1)), 1);
+    }
+
+    #[test]
+    fn board_set_cells_and_rects() {
+        let mut board = Board::with_size(6, 5);
+        assert_eq!(board.set_cells(&[(1, 1), (2, 2), (2, 2)], true).unwrap(), 2);
+        assert_eq!(board.set_cells(&[(1, 1)], true).unwrap(), 0);
+        assert!(matches!(
+            board.set_cells(&[(3, 3), (7, 1)], true),
+            Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords: (7, 1),
+                board: (6, 5)
+            }))
+        ));
+        assert_eq!(board.population(), 2);
+
+        assert_eq!(board.set_rect(2, 2, 3, 4, true), 5);
+        assert_eq!(board.set_rect(3, 4, 2, 2, true), 0);
+        assert_eq!(board.population(), 7);
+        assert_eq!(board.clear_rect(1, 1, 2, 2), 2);
+        assert_eq!(board.population(), 5);
+        assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+        assert_eq!(
+            board,
+            Board::from_rows(&["......", "..#...", ".##...", ".##...", "......"]).unwrap()
+        );
+
+        // Clipped to the board, so this covers all of it
+        let mut full = Board::with_size(6, 5);
+        full.fill();
+        assert_eq!(board.set_rect(0, 0, 100, 100, true), 25);
+        assert_eq!(board, full);
+        assert_eq!(board.clear_rect(0, 0, 100, 100), 30);
+        assert_eq!(board.population(), 0);
+    }
+
+    #[test]
+    fn board_symmetrize() {
+        let rows = ["#..#.", ".##..", "...#.", "#...#"];
+        let symmetrized = |axis| {
+            let mut board = Board::from_rows(&rows).unwrap();
+            assert!(!board.is_symmetric(axis));
+            board.symmetrize(axis);
+            assert!(board.is_symmetric(axis));
+            assert_eq!(board.population(), board.live_cells().count());
+            assert_eq!(board.zobrist_hash(), board.compute_zobrist());
+            board
+        };
+
+        let board = symmetrized(SymmetryAxis::Horizontal);
+        assert!(board.is_horizontally_symmetric());
+        assert_eq!(
+            board,
+            Board::from_rows(&["#...#", ".###.", ".....", "#...#"]).unwrap()
+        );
+        assert_eq!(
+            symmetrized(SymmetryAxis::Vertical),
+            Board::from_rows(&["#..#.", ".##..", ".##..", "#..#."]).unwrap()
+        );
+        // Only the 4x4 square in the corner has a diagonal
+        assert_eq!(
+            symmetrized(SymmetryAxis::Diagonal),
+            Board::from_rows(&["#..#.", ".#...", ".....", "#...#"]).unwrap()
+        );
+        assert_eq!(
+            symmetrized(SymmetryAxis::Rotate180),
+            Board::from_rows(&["#..#.", ".##..", "..##.", ".#..#"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn board_alive_in_region() {
+        let board = Board::from_rows(&["#.....", "..#...", ".##...", ".....#"]).unwrap();
+        assert_eq!(board.count_alive_in_region(1, 1, 6, 4), 5);
+        assert_eq!(board.count_alive_in_region(2, 2, 3, 3), 3);
+        assert_eq!(board.count_alive_in_region(3, 3, 2, 2), 3);
+        assert_eq!(board.count_alive_in_region(4, 1, 5, 3), 0);
+        assert!(board.any_alive_in_region(2, 2, 3, 3));
+        assert!(!board.any_alive_in_region(4, 1, 5, 3));
+
+        // Clipped to the board rather than panicking
+        assert_eq!(board.count_alive_in_region(0, 0, 100, 100), 5);
+        assert_eq!(board.count_alive_in_region(6, 4, 50, 50), 1);
+        assert_eq!(board.count_alive_in_region(20, 20, 30, 30), 0);
+        assert!(!board.any_alive_in_region(20, 20, 30, 30));
+        assert_eq!(board.count_alive_in_region(1, 20, 6, 30), 0);
+    }
+
+    #[test]
+    fn board_place_pattern() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut board = Board::with_size(5, 4);
+        assert_eq!(board.place_pattern(&glider, (2, 1)).unwrap(), 5);
+        assert_eq!(
+            board,
+            Board::from_rows(&["..#..", "...#.", ".###.", "....."]).unwrap()
+        );
+        assert_eq!(board.place_pattern(&glider, (2, 1)).unwrap(), 0);
+
+        let mut board = Board::with_size(5, 4);
+        assert!(matches!(
+            board.place_pattern(&glider, (4, 1)),
+            Err(BoardError::OutOfBounds(OutOfBoundsError {
+                coords: (6, 2),
+                board: (5, 4)
+            }))
+        ));
+        assert!(matches!(
+            Board::with_size(2, 4).place_pattern(&glider, (1, 1)),
+            Err(BoardError::PatternTooLarge(PatternTooLargeError {
+                pattern: (3, 3),
+                board: (2, 4)
+            }))
+        ));
+        assert_eq!(board.population(), 0);
+
+        // Every kind of failure keeps its message
+        let err = board.diff(&Board::with_size(3, 3)).unwrap_err();
+        assert!(matches!(
+            err,
+            BoardError::DimensionMismatch(SizeMismatchError {
+                left: (5, 4),
+                right: (3, 3)
+            })
+        ));
+        assert_eq!(err.to_string(), "can't compare a 5x4 board with a 3x3 one");
+    }
+
+    #[test]
+    fn board_glyphs() {
+        let mut board = Board::from_ascii(".#.\n..#\n###", '#').unwrap();
+        assert_eq!(board.to_string(), "░█░\n░░█\n███\n");
+        board.set_glyphs('*', ' ');
+        assert_eq!(board.glyphs(), ('*', ' '));
+        assert_eq!(board.to_string(), " * \n  *\n***\n");
+        // Copies keep the glyphs
+        assert_eq!(board.copy_region(1, 1, 2, 1).to_string(), " *\n");
+    }
+
+    #[test]
+    fn board_is_dead_and_stable() {
+        let mut board = Board::from_ascii("...\n.#.\n...", '#').unwrap();
+        assert!(!board.is_dead());
+        board.tick();
+        assert!(board.is_dead());
+
+        let mut block = Board::from_ascii("....\n.##.\n.##.\n....", '#').unwrap();
+        let prev = block.clone();
+        block.tick();
+        assert!(block.is_stable(&prev));
+        let mut blinker = Board::from_ascii(".....\n.....\n.###.\n.....\n.....", '#').unwrap();
+        let prev = blinker.clone();
+        blinker.tick();
+        assert!(!blinker.is_stable(&prev));
+
+        // A live background isn't dead, even with no cells on the board
+        let mut board = Board::with_size(3, 3);
+        board.set_rule("B0/S".parse().unwrap());
+        board.tick();
+        board.invert();
+        assert_eq!(board.population(), 0);
+        assert!(!board.is_dead());
+    }
+
+    #[test]
+    fn board_activity_map() {
+        let mut board = Board::from_ascii(".....\n.##..\n.##..\n.....", '#').unwrap();
+        assert!(board.activity_map().is_empty());
+        board.set_track_activity(true);
+        board.tick_n(3);
+        // Only the block's four cells are ever alive
+        let mut expected = [0; 20];
+        for &i in [6, 7, 11, 12].iter() {
+            expected[i] = 3;
+        }
+        assert_eq!(board.activity_map(), &expected[..]);
+
+        board.reset_activity();
+        assert!(board.activity_map().iter().all(|&count| count == 0));
+
+        // The counts stay with their cells as the board grows
+        board.set_grow(Some((20, 20)));
+        board.tick();
+        assert_eq!((board.width(), board.height()), (20, 20));
+        let width = board.width();
+        let activity = board.activity_map();
+        assert_eq!(activity.len(), width * board.height());
+        assert_eq!(activity.iter().sum::<u32>(), 4);
+        let (x, y) = ((2 - board.offset.0) as usize, (2 - board.offset.1) as usize);
+        assert_eq!(activity[(y - 1) * width + (x - 1)], 1);
+
+        board.set_track_activity(false);
+        assert!(board.activity_map().is_empty());
+    }
+
+    #[test]
+    fn board_edge_events() {
+        // A glider heading down and to the right on a small board
+        let glider = ".#....\n..#...\n###...\n......\n......\n......";
+        let mut board = Board::from_ascii(glider, '#').unwrap();
+        board.tick_n(20);
+        assert!(board.take_edge_events().is_empty());
+
+        let mut board = Board::from_ascii(glider, '#').unwrap();
+        board.set_detect_edges(true);
+        board.tick_n(20);
+        let events = board.take_edge_events();
+        assert!(!events.is_empty());
+        assert!(events
+            .iter()
+            .all(|event| event.edge == Edge::Right || event.edge == Edge::Bottom));
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].generation <= pair[1].generation));
+        assert!(events
+            .iter()
+            .all(|event| event.cells > 0 && event.generation <= 20));
+        assert!(board.take_edge_events().is_empty());
+
+        // A blinker in the middle never reaches an edge
+        let mut board = Board::from_ascii(".....\n.....\n.###.\n.....\n.....", '#').unwrap();
+        board.set_detect_edges(true);
+        board.tick_n(10);
+        assert!(board.take_edge_events().is_empty());
+
+        // Nor does anything on a wrapped board
+        let mut board = Board::from_ascii(glider, '#').unwrap();
+        board.set_wrap(true);
+        board.set_detect_edges(true);
+        board.tick_n(20);
+        assert!(board.take_edge_events().is_empty());
+        assert_eq!(board.population(), 5);
+    }
+
+    #[test]
+    fn board_tick_n() {
+        let mut looped = Board::with_size(30, 30);
+        looped.reset_random(9, 0.4);
+        let mut jumped = looped.clone();
+        for _ in 0..10 {
+            looped.tick();
+        }
+        assert_eq!(jumped.tick_n(10), 10);
+        assert_eq!(jumped, looped);
+        assert_eq!(jumped.generation(), 10);
+        assert_eq!(jumped.zobrist_hash(), looped.zobrist_hash());
+        assert_eq!(
+            (jumped.births(), jumped.deaths()),
+            (looped.births(), looped.deaths())
+        );
+
+        // One undo takes back every generation
+        let mut start = Board::with_size(30, 30);
+        start.reset_random(9, 0.4);
+        assert!(jumped.undo());
+        assert_eq!(jumped, start);
+        assert_eq!(jumped.generation(), 0);
+
+        assert_eq!(jumped.tick_n(0), 0);
+        assert_eq!(jumped.generation(), 0);
+    }
+
+    #[test]
+    fn board_tick_diff() {
+        let mut board = Board::with_size(40, 30);
+        board.reset_random(5, 0.4);
+        for _ in 0..20 {
+            let mut copy = board.clone();
+            let changes = board.tick_diff();
+            assert_eq!(changes.len(), board.births() + board.deaths());
+            for &(x, y, alive) in &changes {
+                assert_ne!(copy.get_cell((x, y)).unwrap().alive, alive);
+                copy.set_alive((x, y), alive).unwrap();
+            }
+            assert_eq!(copy, board);
+        }
+
+        // A still life changes nothing
+        let mut block = Board::from_rows(&["....", ".##.", ".##.", "...."]).unwrap();
+        assert!(block.tick_diff().is_empty());
+        assert_eq!(block.generation(), 1);
+    }
+
+    #[test]
+    fn board_tick_n_skips_cycles() {
+        // A blinker repeats after two generations, so it's only run
+        // until the repeat is seen, then once more for an odd count
+        let mut blinker = Board::from_rows(&[".....", "..#..", "..#..", "..#..", "....."]).unwrap();
+        let mut flipped = blinker.clone();
+        flipped.tick();
+        assert_eq!(blinker.tick_n(3), 3);
+        assert_eq!(blinker.generation(), 3);
+        assert_eq!(
+            blinker.live_cells().collect::<Vec<_>>(),
+            flipped.live_cells().collect::<Vec<_>>()
+        );
+        assert_eq!(blinker.tick_n(1_000_000), 2);
+        assert_eq!(blinker.generation(), 1_000_003);
+        assert_eq!(
+            blinker.live_cells().collect::<Vec<_>>(),
+            flipped.live_cells().collect::<Vec<_>>()
+        );
+
+        let mut block = Board::from_rows(&["....", ".##.", ".##.", "...."]).unwrap();
+        assert_eq!(block.tick_n(1000), 1);
+        assert_eq!(block.generation(), 1000);
+
+        // Activity needs every generation
+        let mut tracked = Board::from_rows(&["....", ".##.", ".##.", "...."]).unwrap();
+        tracked.set_track_activity(true);
+        assert_eq!(tracked.tick_n(1000), 1000);
+    }
+
+    #[test]
+    fn board_tick_n_growing() {
+        // A glider heading up-left comes back to the same board
+        // positions every time the board grows under it, which mustn't
+        // be taken for a cycle
+        let rows = ["...##..", "...#.#.", "...#..."];
+        let mut looped = Board::from_rows(&rows).unwrap();
+        looped.set_grow(Some((400, 400)));
+        let mut jumped = looped.clone();
+        for _ in 0..400 {
+            looped.tick();
+        }
+        assert_eq!(jumped.tick_n(400), 400);
+        assert_eq!(jumped, looped);
+        assert_eq!(
+            (jumped.width(), jumped.height()),
+            (looped.width(), looped.height())
+        );
+        assert_eq!(jumped.offset(), looped.offset());
+        assert_eq!(jumped.generation(), 400);
+    }
+
+    #[test]
+    fn board_hooks() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut board = Board::from_rows(&[".....", "..#..", "..#..", "..#..", "....."]).unwrap();
+        let log = Arc::clone(&seen);
+        board.on_tick(move |board| log.lock().unwrap().push(board.generation()));
+        let log = Arc::clone(&seen);
+        board.on_tick(move |board| log.lock().unwrap().push(board.population() as u64 * 100));
+
+        board.tick();
+        assert_eq!(*seen.lock().unwrap(), vec![1, 300]);
+
+        // Every generation is run and seen, even though the blinker
+        // repeats
+        seen.lock().unwrap().clear();
+        assert_eq!(board.tick_n(3), 3);
+        assert_eq!(*seen.lock().unwrap(), vec![2, 300, 3, 300, 4, 300]);
+
+        seen.lock().unwrap().clear();
+        board.remove_all_hooks();
+        board.tick();
+        assert!(seen.lock().unwrap().is_empty());
+        assert_eq!(board.tick_n(1001), 3);
+    }
+
+    #[test]
+    fn board_from_ascii() {
+        let art = "
+            ..O
+            O.O
+            .OO
+        ";
+        // Indentation counts as dead cells, so this is 15 cells wide
+        let board = Board::from_ascii(art, 'O').unwrap();
+        assert_eq!((board.width(), board.height()), (15, 3));
+        assert_eq!(board.population(), 5);
+        assert!(board.is_alive_at((15, 1)) && board.is_alive_at((13, 2)));
+
+        // Short lines are padded with dead cells
+        let board = Board::from_ascii("#\n.#.#\n", '#').unwrap();
+        assert_eq!(
+            board.to_grid(),
+            vec![
+                vec![true, false, false, false],
+                vec![false, true, false, true]
+            ]
+        );
+
+        assert!(Board::from_ascii("\n  \n", '#').is_err());
+    }
+
+    #[test]
+    fn board_tick_stats() {
+        let mut board = Board::from_ascii(".....\n.....\n.###.\n.....\n.....", '#').unwrap();
+        assert_eq!((board.births(), board.deaths()), (0, 0));
+        // Each turn of a blinker loses its two ends and gains two new ones
+        for generation in 1..=4 {
+            let stats = board.tick();
+            assert_eq!(
+                stats,
+                TickStats {
+                    generation,
+                    births: 2,
+                    deaths: 2,
+                    population_before: 3,
+                    population_after: 3,
+                }
+            );
+            assert_eq!(stats.changed(), 4);
+            assert_eq!((board.births(), board.deaths()), (2, 2));
+        }
+
+        // Every way of ticking counts the same, and the counts agree
+        // with the change in population
+        let mut serial = Board::with_size(30, 30);
+        serial.reset_random(5, 0.4);
+        let mut strips = serial.clone();
+        strips.set_parallelism(Parallelism::Strips(Some(3)));
+        for _ in 0..10 {
+            let stats = serial.tick();
+            assert_eq!(stats, strips.tick());
+            assert_eq!(
+                stats.population_after + stats.deaths,
+                stats.population_before + stats.births
+            );
+            assert_eq!(stats.population_after, serial.count_population());
+        }
+
+        // The background coming to life under B0 is counted too
+        let mut board = Board::with_size(4, 4);
+        board.set_rule("B0/S8".parse().unwrap());
+        board.tick();
+        assert_eq!((board.births(), board.deaths()), (16, 0));
+
+        board.clear();
+        assert_eq!((board.births(), board.deaths()), (0, 0));
+    }
+
+    // Width, height, density, seed and wrapping of a random board. The
+    // settings are generated rather than the board, so a failing case
+    // shrinks to a few numbers rather than a grid of cells.
+    type BoardParams = (usize, usize, f64, u64, bool);
+
+    fn board_params() -> impl Strategy<Value = BoardParams> {
+        // Boards of up to 24x24 cells, from empty to full, with the
+        // edges wrapped or not
+        (
+            1..=24usize,
+            1..=24usize,
+            0.0..=1.0f64,
+            any::<u64>(),
+            any::<bool>(),
+        )
+    }
+
+    fn build((width, height, density, seed, wrap): BoardParams) -> Board {
+        let mut board = Board::with_size(width, height);
+        board.reset_random(seed, density);
+        board.set_wrap(wrap);
+        board
+    }
+
+    fn recount(board: &Board, (x, y): (usize, usize)) -> u32 {
+        // Neighbours counted the long way, for checking the fast path
+        let mut count = 0;
+        for &(dx, dy) in NEIGHBOUR_OFFSETS.iter() {
+            let (mut nx, mut ny) = (x as isize + dx, y as isize + dy);
+            if board.wrap {
+                nx = (nx - 1).rem_euclid(board.width as isize) + 1;
+                ny = (ny - 1).rem_euclid(board.height as isize) + 1;
+            }
+            let on_board = (1..=board.width as isize).contains(&nx)
+                && (1..=board.height as isize).contains(&ny);
+            if on_board && board.board[nx as usize][ny as usize].alive {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    proptest! {
+        #[test]
+        fn board_prop_neighbour_counts(params in board_params(), ticks in 0..3usize) {
+            // Before and after ticking, every cell has between 0 and 8
+            // alive neighbours, wherever it is on the board
+            let mut board = build(params);
+            for _ in 0..ticks {
+                board.tick();
+            }
+            for x in 1..=board.width {
+                for y in 1..=board.height {
+                    let count = board.count_adjacent_alive((x, y));
+                    prop_assert!(count <= 8);
+                    prop_assert_eq!(count, recount(&board, (x, y)));
+                }
+            }
+        }
+
+        #[test]
+        fn board_prop_block_is_still(
+            width in 6..=24usize,
+            height in 6..=24usize,
+            x in 0..1000usize,
+            y in 0..1000usize,
+        ) {
+            // A block with at least a dead cell between it and the edges
+            let (x, y) = (2 + x % (width - 4), 2 + y % (height - 4));
+            let mut board = Board::with_size(width, height);
+            for &coords in [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)].iter() {
+                board.set_alive(coords, true).unwrap();
+            }
+            let before = board.clone();
+            let stats = board.tick();
+            prop_assert_eq!(board, before);
+            prop_assert_eq!(stats.changed(), 0);
+        }
+
+        #[test]
+        fn board_prop_invert_twice(params in board_params()) {
+            let board = build(params);
+            let mut inverted = board.clone();
+            inverted.invert();
+            prop_assert_eq!(
+                inverted.population(),
+                board.width * board.height - board.population()
+            );
+            inverted.invert();
+            prop_assert_eq!(inverted.zobrist_hash(), board.zobrist_hash());
+            prop_assert_eq!(inverted, board);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// Several dense boards advanced together, for comparing how the same
+// start plays out under different rules or settings.
+
+use crate::board::Board;
+
+#[derive(Clone, Default)]
+pub struct BoardSet {
+    pub boards: Vec<Board>,
+}
+
+impl BoardSet {
+    pub fn new(boards: Vec<Board>) -> BoardSet {
+        BoardSet { boards }
+    }
+
+    pub fn tick_all(&mut self) {
+        // Advances every board by one generation
+        for board in self.boards.iter_mut() {
+            board.tick();
+        }
+    }
+
+    pub fn diff(&self, a: usize, b: usize) -> Vec<(usize, usize)> {
+        // Board coordinates of the cells that are alive on one of boards
+        // `a` and `b` but not the other, column by column. Where one
+        // board is smaller, its missing cells count as dead. Panics if
+        // either index is out of range.
+
+        let (a, b) = (&self.boards[a], &self.boards[b]);
+        let alive = |board: &Board, coords| board.get_cell(coords).is_some_and(|cell| cell.alive);
+        let mut differences = Vec::new();
+        for x in 1..=a.width().max(b.width()) {
+            for y in 1..=a.height().max(b.height()) {
+                if alive(a, (x, y)) != alive(b, (x, y)) {
+                    differences.push((x, y));
+                }
+            }
+        }
+        differences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::Rule;
+
+    #[test]
+    fn boardset_diff() {
+        let mut set = BoardSet::new(vec![
+            Board::from_ascii("....\n.##.\n.##.\n....", '#').unwrap(),
+            Board::from_ascii("....\n.##.\n.#..\n....", '#').unwrap(),
+        ]);
+        assert_eq!(set.diff(0, 1), vec![(3, 3)]);
+        // The pre-block fills itself in
+        set.tick_all();
+        assert!(set.diff(0, 1).is_empty());
+
+        set.boards.push(Board::with_size(2, 2));
+        assert_eq!(set.diff(0, 2), vec![(2, 2), (2, 3), (3, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn boardset_rules_diverge() {
+        // The same soup under Conway's rule and HighLife
+        let mut conway = Board::with_size(40, 40);
+        conway.reset_random(3, 0.4);
+        let mut highlife = conway.clone();
+        highlife.set_rule(Rule::new(&[3, 6], &[2, 3]).unwrap());
+        let mut set = BoardSet::new(vec![conway, highlife]);
+        assert!(set.diff(0, 1).is_empty());
+        for _ in 0..10 {
+            set.tick_all();
+        }
+        assert!(!set.diff(0, 1).is_empty());
+        assert!(set.boards.iter().all(|board| board.generation() == 10));
+        assert!(set.diff(1, 1).is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+// Builder for setting up a dense board in one expression.
+
+use crate::{
+    board::{Board, BorderMode, BOARD_HEIGHT, BOARD_WIDTH},
+    error::{GolError, PatternTooLargeError},
+    rule::Rule,
+};
+
+// Serializable, so the settings for a run can be saved alongside it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardBuilder {
+    width: usize,
+    height: usize,
+    rule: Rule,
+    wrap: bool,
+    border_mode: BorderMode,
+    // Largest size the board may grow to, or None to keep it fixed
+    grow: Option<(usize, usize)>,
+    // Chance of each cell starting alive. 0 leaves the board empty.
+    density: f64,
+    // Seed for the random cells, or None for a different board each time
+    seed: Option<u64>,
+    // Alive cells placed in the middle of the board, as offsets from
+    // the top-left of their bounding box
+    pattern: Option<Vec<(usize, usize)>>,
+}
+
+impl BoardBuilder {
+    pub fn new() -> BoardBuilder {
+        // Starts from the same settings as Board::new
+        BoardBuilder {
+            width: BOARD_WIDTH - 1,
+            height: BOARD_HEIGHT - 1,
+            rule: Rule::default(),
+            wrap: false,
+            border_mode: BorderMode::default(),
+            grow: None,
+            density: 0.0,
+            seed: None,
+            pattern: None,
+        }
+    }
+
+    pub fn size(mut self, width: usize, height: usize) -> BoardBuilder {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule) -> BoardBuilder {
+        self.rule = rule;
+        self
+    }
+
+    pub fn wrap(mut self, wrap: bool) -> BoardBuilder {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn border_mode(mut self, border_mode: BorderMode) -> BoardBuilder {
+        self.border_mode = border_mode;
+        self
+    }
+
+    pub fn grow(mut self, max_size: Option<(usize, usize)>) -> BoardBuilder {
+        self.grow = max_size;
+        self
+    }
+
+    pub fn density(mut self, density: f64) -> BoardBuilder {
+        self.density = density;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> BoardBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn pattern(mut self, cells: &[(usize, usize)]) -> BoardBuilder {
+        // Places the cells in the middle of the board, on top of any
+        // random ones
+        self.pattern = Some(cells.to_vec());
+        self
+    }
+
+    pub fn build(&self) -> Result<Board, GolError> {
+        // Checks the settings before allocating anything, then
+        // builds the board and fills in its random cells.
+
+        if self.width == 0 || self.height == 0 {
+            return Err(GolError::InvalidSize {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        if !(0.0..=1.0).contains(&self.density) {
+            return Err(GolError::InvalidDensity(self.density));
+        }
+        let pattern_size = self.pattern.as_ref().map(|cells| {
+            let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+            let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+            (width, height)
+        });
+        if let Some((width, height)) = pattern_size {
+            if width > self.width || height > self.height {
+                return Err(GolError::PatternTooLarge(PatternTooLargeError {
+                    pattern: (width, height),
+                    board: (self.width, self.height),
+                }));
+            }
+        }
+
+        let mut board = Board::with_size(self.width, self.height);
+        board.set_rule(self.rule);
+        board.set_wrap(self.wrap);
+        board.set_border_mode(self.border_mode);
+        board.set_grow(self.grow);
+        if self.density > 0.0 {
+            board.reset_random(self.seed.unwrap_or_else(rand::random), self.density);
+        }
+        if let (Some(cells), Some((width, height))) = (&self.pattern, pattern_size) {
+            let origin = (1 + (self.width - width) / 2, 1 + (self.height - height) / 2);
+            board
+                .place_pattern(cells, origin)
+                .expect("pattern was checked to fit");
+        }
+        Ok(board)
+    }
+}
+
+impl Default for BoardBuilder {
+    fn default() -> BoardBuilder {
+        BoardBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::life::Life;
+
+    #[test]
+    fn builder_defaults() {
+        let board = BoardBuilder::new().build().unwrap();
+        assert_eq!(board, Board::new());
+        assert_eq!(board.rule(), Rule::CONWAY);
+        assert!(!board.wrap());
+        assert_eq!(board.border_mode(), Board::new().border_mode());
+    }
+
+    #[test]
+    fn builder_settings() {
+        let highlife: Rule = "B36/S23".parse().unwrap();
+        let builder = BoardBuilder::new()
+            .size(30, 20)
+            .rule(highlife)
+            .wrap(true)
+            .density(0.3)
+            .seed(7);
+        let board = builder.build().unwrap();
+        assert_eq!((board.width(), board.height()), (30, 20));
+        assert_eq!(board.rule(), highlife);
+        assert!(board.wrap());
+        assert!(board.population() > 0);
+
+        // The same seed gives the same cells
+        let mut expected = Board::with_size(30, 20);
+        expected.reset_random(7, 0.3);
+        assert_eq!(board, expected);
+        assert_eq!(builder.build().unwrap(), board);
+    }
+
+    #[test]
+    fn builder_pattern() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let board = BoardBuilder::new()
+            .size(6, 5)
+            .border_mode(BorderMode::Alive)
+            .pattern(&glider)
+            .build()
+            .unwrap();
+        assert_eq!(board.border_mode(), BorderMode::Alive);
+        let cells: Vec<_> = board.live_cells().collect();
+        assert_eq!(cells, vec![(3, 2), (4, 3), (2, 4), (3, 4), (4, 4)]);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_settings() {
+        assert_eq!(
+            BoardBuilder::new().size(0, 5).build().err(),
+            Some(GolError::InvalidSize {
+                width: 0,
+                height: 5
+            })
+        );
+        for &density in [-0.1, 1.5, f64::NAN].iter() {
+            assert!(matches!(
+                BoardBuilder::new().density(density).build(),
+                Err(GolError::InvalidDensity(_))
+            ));
+        }
+        assert_eq!(
+            BoardBuilder::new()
+                .size(2, 5)
+                .pattern(&[(0, 0), (2, 0)])
+                .build()
+                .err(),
+            Some(GolError::PatternTooLarge(PatternTooLargeError {
+                pattern: (3, 1),
+                board: (2, 5)
+            }))
+        );
+    }
+}
@@ -0,0 +1,313 @@
+// Keyboard controls for the interactive terminal view.
+
+use std::time::Duration;
+
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+
+use crate::{
+    life::Life,
+    render::tui::{MAX_ZOOM, MIN_ZOOM},
+};
+
+// Starting time between generations, and how far `+`/`-` change it.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+const DELAY_STEP: Duration = Duration::from_millis(25);
+const MIN_DELAY: Duration = DELAY_STEP;
+const MAX_DELAY: Duration = Duration::from_millis(1000);
+
+pub struct Controls {
+    pub paused: bool,
+    // Time between generations while running
+    pub delay: Duration,
+    // While editing, which is only possible when paused, the
+    // cursor can be moved around to flip cells.
+    pub editing: bool,
+    pub cursor: (i64, i64),
+    // Number of cells along each side of a character on screen
+    pub zoom_level: u8,
+    pub quit: bool,
+    // Set by `.` until the next generation has been taken
+    step_requested: bool,
+    // Stable coordinates of the top-left cell in view, and the
+    // size of the view, which the cursor can't leave.
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+}
+
+impl Controls {
+    pub fn new(origin: (i64, i64), width: i64, height: i64) -> Controls {
+        Controls {
+            paused: false,
+            delay: DEFAULT_DELAY,
+            editing: false,
+            cursor: origin,
+            zoom_level: MIN_ZOOM,
+            quit: false,
+            step_requested: false,
+            origin,
+            width,
+            height,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, life: &mut dyn Life) {
+        // Applies a single keypress to the controls, and to the board
+        // when it edits a cell. Key releases are ignored.
+
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                // Raw mode swallows the interrupt signal, so Ctrl+C is handled here
+                KeyCode::Char('c') => self.quit = true,
+                // Stepping through history pauses, so the simulation
+                // doesn't immediately run on again
+                KeyCode::Char('z') => {
+                    self.paused = true;
+                    life.undo();
+                }
+                KeyCode::Char('y') => {
+                    self.paused = true;
+                    life.redo();
+                }
+                _ => (),
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('e') if self.paused => self.editing = !self.editing,
+            KeyCode::Left if self.editing => self.move_cursor(-1, 0),
+            KeyCode::Right if self.editing => self.move_cursor(1, 0),
+            KeyCode::Up if self.editing => self.move_cursor(0, -1),
+            KeyCode::Down if self.editing => self.move_cursor(0, 1),
+            KeyCode::Enter | KeyCode::Char(' ') if self.editing => life.flip_at(self.cursor),
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('.') if self.paused => self.step_requested = true,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.delay = self.delay.saturating_sub(DELAY_STEP).max(MIN_DELAY)
+            }
+            KeyCode::Char('-') => self.delay = (self.delay + DELAY_STEP).min(MAX_DELAY),
+            _ => (),
+        }
+    }
+
+    pub fn handle_mouse(
+        &mut self,
+        mouse: MouseEvent,
+        cell: Option<(i64, i64)>,
+        life: &mut dyn Life,
+    ) {
+        // Applies a mouse event. `cell` is the cell under the pointer,
+        // if it's over the board. A left click flips that cell, and
+        // scrolling zooms in and out.
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(cell) = cell {
+                    life.flip_at(cell);
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                self.zoom_level = self.zoom_level.saturating_sub(1).max(MIN_ZOOM)
+            }
+            MouseEventKind::ScrollDown => self.zoom_level = (self.zoom_level + 1).min(MAX_ZOOM),
+            _ => (),
+        }
+    }
+
+    pub fn take_step(&mut self) -> bool {
+        // Whether a single step was requested since the last call
+        let step_requested = self.step_requested;
+        self.step_requested = false;
+        step_requested
+    }
+
+    pub fn status(&self) -> String {
+        if self.editing {
+            format!("Editing  Cursor: ({}, {})", self.cursor.0, self.cursor.1)
+        } else if self.paused {
+            String::from("Paused")
+        } else {
+            format!("Running  Delay: {}ms", self.delay.as_millis())
+        }
+    }
+
+    pub fn hints(&self) -> &'static str {
+        // The keys that do something right now
+        if self.editing {
+            "[arrows] move [enter] flip [e] done"
+        } else if self.paused {
+            "[space] resume [.] step [ctrl+z/y] undo/redo [e] edit [q] quit"
+        } else {
+            "[space] pause [+/-] speed [click] flip [scroll] zoom [q] quit"
+        }
+    }
+
+    fn move_cursor(&mut self, dx: i64, dy: i64) {
+        // Moves the cursor, keeping it inside the view
+        let x = (self.cursor.0 + dx).clamp(self.origin.0, self.origin.0 + self.width - 1);
+        let y = (self.cursor.1 + dy).clamp(self.origin.1, self.origin.1 + self.height - 1);
+        self.cursor = (x, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+
+    fn press(controls: &mut Controls, board: &mut Board, code: KeyCode) {
+        controls.handle_key(KeyEvent::new(code, KeyModifiers::NONE), board);
+    }
+
+    #[test]
+    fn controls_edit_flips_cell() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 10, 10);
+
+        press(&mut controls, &mut board, KeyCode::Char('e'));
+        assert!(!controls.editing);
+        press(&mut controls, &mut board, KeyCode::Char(' '));
+        press(&mut controls, &mut board, KeyCode::Char('e'));
+        assert!(controls.editing);
+        press(&mut controls, &mut board, KeyCode::Right);
+        press(&mut controls, &mut board, KeyCode::Down);
+        press(&mut controls, &mut board, KeyCode::Down);
+        assert_eq!(controls.cursor, (2, 3));
+        assert_eq!(controls.status(), "Editing  Cursor: (2, 3)");
+        assert!(controls.hints().contains("[enter] flip"));
+
+        press(&mut controls, &mut board, KeyCode::Enter);
+        assert!(board.get_cell((2, 3)).unwrap().alive);
+        press(&mut controls, &mut board, KeyCode::Char(' '));
+        assert!(!board.get_cell((2, 3)).unwrap().alive);
+
+        press(&mut controls, &mut board, KeyCode::Char('e'));
+        assert!(!controls.editing);
+        assert!(controls.paused);
+        press(&mut controls, &mut board, KeyCode::Enter);
+        assert!(!board.get_cell((2, 3)).unwrap().alive);
+    }
+
+    #[test]
+    fn controls_cursor_stays_in_view() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 3, 3);
+
+        press(&mut controls, &mut board, KeyCode::Char(' '));
+        press(&mut controls, &mut board, KeyCode::Char('e'));
+        press(&mut controls, &mut board, KeyCode::Up);
+        press(&mut controls, &mut board, KeyCode::Left);
+        assert_eq!(controls.cursor, (1, 1));
+        for _ in 0..5 {
+            press(&mut controls, &mut board, KeyCode::Right);
+            press(&mut controls, &mut board, KeyCode::Down);
+        }
+        assert_eq!(controls.cursor, (3, 3));
+    }
+
+    #[test]
+    fn controls_pause_and_step() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 10, 10);
+
+        // Stepping does nothing while running
+        press(&mut controls, &mut board, KeyCode::Char('.'));
+        assert!(!controls.take_step());
+
+        press(&mut controls, &mut board, KeyCode::Char(' '));
+        assert!(controls.paused);
+        press(&mut controls, &mut board, KeyCode::Char('.'));
+        assert!(controls.take_step());
+        assert!(!controls.take_step());
+
+        press(&mut controls, &mut board, KeyCode::Char(' '));
+        assert!(!controls.paused);
+    }
+
+    #[test]
+    fn controls_delay() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 10, 10);
+
+        press(&mut controls, &mut board, KeyCode::Char('-'));
+        assert_eq!(controls.delay, DEFAULT_DELAY + DELAY_STEP);
+        press(&mut controls, &mut board, KeyCode::Char('+'));
+        assert_eq!(controls.delay, DEFAULT_DELAY);
+
+        for _ in 0..100 {
+            press(&mut controls, &mut board, KeyCode::Char('+'));
+        }
+        assert_eq!(controls.delay, MIN_DELAY);
+        for _ in 0..100 {
+            press(&mut controls, &mut board, KeyCode::Char('-'));
+        }
+        assert_eq!(controls.delay, MAX_DELAY);
+    }
+
+    #[test]
+    fn controls_mouse() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 10, 10);
+        let mouse = |kind| MouseEvent {
+            kind,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        let click = mouse(MouseEventKind::Down(MouseButton::Left));
+        controls.handle_mouse(click, Some((3, 4)), &mut board);
+        assert!(board.get_cell((3, 4)).unwrap().alive);
+        controls.handle_mouse(click, Some((3, 4)), &mut board);
+        assert!(!board.get_cell((3, 4)).unwrap().alive);
+        // Clicks off the board do nothing
+        controls.handle_mouse(click, None, &mut board);
+        assert_eq!(board.population(), 0);
+
+        assert_eq!(controls.zoom_level, MIN_ZOOM);
+        for _ in 0..5 {
+            controls.handle_mouse(mouse(MouseEventKind::ScrollDown), None, &mut board);
+        }
+        assert_eq!(controls.zoom_level, MAX_ZOOM);
+        for _ in 0..5 {
+            controls.handle_mouse(mouse(MouseEventKind::ScrollUp), None, &mut board);
+        }
+        assert_eq!(controls.zoom_level, MIN_ZOOM);
+    }
+
+    #[test]
+    fn controls_quit() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 10, 10);
+
+        press(&mut controls, &mut board, KeyCode::Char('q'));
+        assert!(controls.quit);
+    }
+
+    #[test]
+    fn controls_undo_redo() {
+        let mut board = Board::new();
+        let mut controls = Controls::new((1, 1), 10, 10);
+        for coords in [(4, 3), (4, 4), (4, 5)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        let start = board.clone();
+        board.tick();
+        let ticked = board.clone();
+
+        let ctrl = |code| KeyEvent::new(code, KeyModifiers::CONTROL);
+        controls.handle_key(ctrl(KeyCode::Char('z')), &mut board);
+        assert!(controls.paused);
+        assert_eq!(board, start);
+        controls.handle_key(ctrl(KeyCode::Char('y')), &mut board);
+        assert_eq!(board, ticked);
+    }
+}
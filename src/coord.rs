@@ -0,0 +1,102 @@
+// Signed cell coordinates, counting from 0 at the top-left, so that a
+// step off the edge of a board gives a coordinate that can be checked
+// rather than an underflow. Board::index_of says whether one is on a
+// given board.
+
+// Steps from a cell to each of its eight neighbours, in the order
+// Coord::neighbors and Board::get_neighbors give them: NW, N, NE, W,
+// E, SW, S, SE
+pub(crate) const NEIGHBOUR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Coord {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Coord {
+    pub fn new(x: i64, y: i64) -> Coord {
+        Coord { x, y }
+    }
+
+    pub fn offset(self, dx: i64, dy: i64) -> Coord {
+        // The coordinate `dx` across and `dy` down from this one. Steps
+        // past the i64 bounds stop at them rather than overflowing.
+        Coord {
+            x: self.x.saturating_add(dx),
+            y: self.y.saturating_add(dy),
+        }
+    }
+
+    pub fn neighbors(self) -> [Coord; 8] {
+        // The eight coordinates around this one, in the order NW, N, NE,
+        // W, E, SW, S, SE
+        NEIGHBOUR_OFFSETS.map(|(dx, dy)| self.offset(dx as i64, dy as i64))
+    }
+}
+
+impl From<(i64, i64)> for Coord {
+    fn from((x, y): (i64, i64)) -> Coord {
+        Coord { x, y }
+    }
+}
+
+impl From<Coord> for (i64, i64) {
+    fn from(coord: Coord) -> (i64, i64) {
+        (coord.x, coord.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coord_offset() {
+        let origin = Coord::new(0, 0);
+        assert_eq!(origin.offset(-1, 2), Coord::new(-1, 2));
+        assert_eq!(origin.offset(-1, -1).offset(1, 1), origin);
+
+        let corner = Coord::new(i64::MAX, i64::MIN);
+        assert_eq!(corner.offset(1, -1), corner);
+        assert_eq!(corner.offset(-1, 1), Coord::new(i64::MAX - 1, i64::MIN + 1));
+        assert_eq!(Coord::new(0, 5).offset(i64::MIN, i64::MAX).y, i64::MAX);
+    }
+
+    #[test]
+    fn coord_neighbors() {
+        let (x, y) = (3, -7);
+        let neighbors: Vec<(i64, i64)> = Coord::new(x, y)
+            .neighbors()
+            .iter()
+            .map(|&coord| coord.into())
+            .collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                (x - 1, y - 1),
+                (x, y - 1),
+                (x + 1, y - 1),
+                (x - 1, y),
+                (x + 1, y),
+                (x - 1, y + 1),
+                (x, y + 1),
+                (x + 1, y + 1)
+            ]
+        );
+
+        // At the bounds the neighbours past them collapse onto the edge
+        let edge = Coord::new(i64::MIN, 0).neighbors();
+        assert_eq!(edge[0], Coord::new(i64::MIN, -1));
+        assert_eq!(edge[3], Coord::new(i64::MIN, 0));
+    }
+}
@@ -0,0 +1,171 @@
+// Spots when a board has settled into a still life or an oscillator.
+
+use std::{collections::VecDeque, fmt};
+
+// Number of recent generations remembered, so the longest period found
+pub const CYCLE_WINDOW: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    // First generation of the repeating states
+    pub generation: u64,
+    // Number of generations before a state comes round again.
+    // 1 for a board that no longer changes.
+    pub period: u64,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stabilized at generation {} with period {}",
+            self.generation, self.period
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleDetector {
+    // Generation and hash of the most recent states, oldest first
+    recent: VecDeque<(u64, u64)>,
+    capacity: usize,
+}
+
+impl CycleDetector {
+    pub fn new(capacity: usize) -> CycleDetector {
+        CycleDetector {
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn record(&mut self, generation: u64, hash: u64) -> Option<Cycle> {
+        // Remembers the hash of the board at `generation`, returning the
+        // cycle it's in if the same hash was seen within the window.
+        // Matching hashes are taken to mean matching boards.
+
+        let cycle = self
+            .recent
+            .iter()
+            .rev()
+            .find(|&&(_, seen)| seen == hash)
+            .map(|&(seen_at, _)| Cycle {
+                generation: seen_at,
+                period: generation - seen_at,
+            });
+        if self.recent.len() == self.capacity {
+            self.recent.pop_front();
+        }
+        if self.capacity > 0 {
+            self.recent.push_back((generation, hash));
+        }
+        cycle
+    }
+
+    pub fn clear(&mut self) {
+        // Forgets every state, for when the board is edited
+        self.recent.clear();
+    }
+}
+
+impl Default for CycleDetector {
+    fn default() -> CycleDetector {
+        CycleDetector::new(CYCLE_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{board::Board, life::Life};
+
+    fn find_cycle(cells: &[(i64, i64)], width: usize, height: usize) -> Option<Cycle> {
+        // Runs a board with the given cells alive until a cycle is
+        // found, giving up after 200 generations
+        let mut board = Board::with_size(width, height);
+        board.set_history_depth(0);
+        for &coords in cells {
+            board.set_alive_at(coords, true);
+        }
+        let mut detector = CycleDetector::default();
+        for _ in 0..200 {
+            if let Some(cycle) = detector.record(board.generation(), board.zobrist_hash()) {
+                return Some(cycle);
+            }
+            board.tick();
+        }
+        None
+    }
+
+    #[test]
+    fn cycle_still_life() {
+        // A block never changes
+        let block = [(3, 3), (4, 3), (3, 4), (4, 4)];
+        let cycle = find_cycle(&block, 6, 6).unwrap();
+        assert_eq!(
+            cycle,
+            Cycle {
+                generation: 0,
+                period: 1
+            }
+        );
+        assert_eq!(
+            cycle.to_string(),
+            "stabilized at generation 0 with period 1"
+        );
+
+        // A pre-block, three cells of it, settles in one generation
+        assert_eq!(
+            find_cycle(&block[..3], 6, 6),
+            Some(Cycle {
+                generation: 1,
+                period: 1
+            })
+        );
+    }
+
+    #[test]
+    fn cycle_blinker() {
+        let blinker = [(2, 3), (3, 3), (4, 3)];
+        assert_eq!(
+            find_cycle(&blinker, 5, 5),
+            Some(Cycle {
+                generation: 0,
+                period: 2
+            })
+        );
+    }
+
+    #[test]
+    fn cycle_pentadecathlon() {
+        // A row of ten cells turns into the pentadecathlon, whose
+        // period is 15, two generations later
+        let row: Vec<(i64, i64)> = (10..20).map(|x| (x, 10)).collect();
+        assert_eq!(
+            find_cycle(&row, 30, 20),
+            Some(Cycle {
+                generation: 2,
+                period: 15
+            })
+        );
+    }
+
+    #[test]
+    fn cycle_window() {
+        // Periods longer than the window go unnoticed
+        let mut detector = CycleDetector::new(3);
+        for generation in 0..10 {
+            assert_eq!(detector.record(generation, generation % 4), None);
+        }
+        assert_eq!(
+            detector.record(10, 3),
+            Some(Cycle {
+                generation: 7,
+                period: 3
+            })
+        );
+
+        detector.clear();
+        assert_eq!(detector.record(11, 3), None);
+    }
+}
@@ -0,0 +1,232 @@
+// Errors returned when building, loading or rearranging a board.
+
+use std::{error, fmt, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GolError {
+    // A pattern that couldn't be parsed. Lines count from 1.
+    Parse {
+        line: usize,
+        message: String,
+    },
+    // A rulestring or list of neighbour counts that isn't a valid rule
+    InvalidRule(String),
+    // A board with no cells, or one too big to allocate
+    InvalidSize {
+        width: usize,
+        height: usize,
+    },
+    // A chance of a cell being alive that isn't between 0 and 1
+    InvalidDensity(f64),
+    // A grid whose rows aren't all the same length. Rows count from 0.
+    RaggedGrid {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    // A pattern bigger than the board it was to be placed on
+    PatternTooLarge(PatternTooLargeError),
+}
+
+impl fmt::Display for GolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GolError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            GolError::InvalidRule(message) => write!(f, "invalid rule: {}", message),
+            GolError::InvalidSize { width, height } if *width == 0 || *height == 0 => {
+                write!(f, "a {}x{} board has no cells", width, height)
+            }
+            GolError::InvalidSize { width, height } => {
+                write!(f, "a {}x{} board is too big", width, height)
+            }
+            GolError::InvalidDensity(density) => {
+                write!(f, "density {} is not between 0 and 1", density)
+            }
+            GolError::RaggedGrid {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, but the first row has {}",
+                row, found, expected
+            ),
+            GolError::PatternTooLarge(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for GolError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternTooLargeError {
+    // Width and height of the pattern's bounding box, and of the board
+    pub pattern: (usize, usize),
+    pub board: (usize, usize),
+}
+
+impl fmt::Display for PatternTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a {}x{} pattern doesn't fit on a {}x{} board",
+            self.pattern.0, self.pattern.1, self.board.0, self.board.1
+        )
+    }
+}
+
+impl error::Error for PatternTooLargeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBoundsError {
+    // Board coordinates that were asked for, and the size of the board
+    pub coords: (usize, usize),
+    pub board: (usize, usize),
+}
+
+impl fmt::Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}) is off a {}x{} board, whose cells run from (1, 1) to ({}, {})",
+            self.coords.0, self.coords.1, self.board.0, self.board.1, self.board.0, self.board.1
+        )
+    }
+}
+
+impl error::Error for OutOfBoundsError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeMismatchError {
+    // Width and height of the two boards being compared
+    pub left: (usize, usize),
+    pub right: (usize, usize),
+}
+
+impl fmt::Display for SizeMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "can't compare a {}x{} board with a {}x{} one",
+            self.left.0, self.left.1, self.right.0, self.right.1
+        )
+    }
+}
+
+impl error::Error for SizeMismatchError {}
+
+// Any of the ways placing cells on a board, comparing boards or
+// loading a saved board can fail, for callers that would rather handle
+// them all in one place
+#[derive(Debug)]
+pub enum BoardError {
+    OutOfBounds(OutOfBoundsError),
+    PatternTooLarge(PatternTooLargeError),
+    DimensionMismatch(SizeMismatchError),
+    // A saved board whose size or rule isn't valid
+    Invalid(GolError),
+    // A saved board that's malformed or cut short, saying what's wrong
+    // with it
+    Corrupt(String),
+    // A saved board in a newer version of its format than can be read
+    UnsupportedVersion { found: u32, supported: u32 },
+    // The file couldn't be read
+    Io(io::Error),
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::OutOfBounds(e) => write!(f, "{}", e),
+            BoardError::PatternTooLarge(e) => write!(f, "{}", e),
+            BoardError::DimensionMismatch(e) => write!(f, "{}", e),
+            BoardError::Invalid(e) => write!(f, "{}", e),
+            BoardError::Corrupt(message) => write!(f, "{}", message),
+            BoardError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "saved in format version {}, but only version {} can be read",
+                found, supported
+            ),
+            BoardError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for BoardError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            BoardError::OutOfBounds(e) => Some(e),
+            BoardError::PatternTooLarge(e) => Some(e),
+            BoardError::DimensionMismatch(e) => Some(e),
+            BoardError::Invalid(e) => Some(e),
+            BoardError::Corrupt(_) | BoardError::UnsupportedVersion { .. } => None,
+            BoardError::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<OutOfBoundsError> for BoardError {
+    fn from(e: OutOfBoundsError) -> BoardError {
+        BoardError::OutOfBounds(e)
+    }
+}
+
+impl From<PatternTooLargeError> for BoardError {
+    fn from(e: PatternTooLargeError) -> BoardError {
+        BoardError::PatternTooLarge(e)
+    }
+}
+
+impl From<SizeMismatchError> for BoardError {
+    fn from(e: SizeMismatchError) -> BoardError {
+        BoardError::DimensionMismatch(e)
+    }
+}
+
+impl From<GolError> for BoardError {
+    fn from(e: GolError) -> BoardError {
+        BoardError::Invalid(e)
+    }
+}
+
+impl From<io::Error> for BoardError {
+    fn from(e: io::Error) -> BoardError {
+        BoardError::Io(e)
+    }
+}
+
+#[cfg(feature = "image")]
+#[derive(Debug)]
+pub enum ImageLoadError {
+    // The file couldn't be read, or isn't an image that can be decoded
+    Image(image::ImageError),
+    // An image with no pixels, which would make a board with no cells
+    Empty,
+}
+
+#[cfg(feature = "image")]
+impl fmt::Display for ImageLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageLoadError::Image(e) => write!(f, "{}", e),
+            ImageLoadError::Empty => write!(f, "the image has no pixels"),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl error::Error for ImageLoadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ImageLoadError::Image(e) => Some(e),
+            ImageLoadError::Empty => None,
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for ImageLoadError {
+    fn from(e: image::ImageError) -> ImageLoadError {
+        ImageLoadError::Image(e)
+    }
+}
@@ -0,0 +1,120 @@
+// Spots when every cell on a board has died, keeping track of how
+// large the population got before it did.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extinction {
+    // First generation with no alive cells
+    pub generation: u64,
+    // Largest population seen, and the first generation it was seen in
+    pub peak: usize,
+    pub peak_generation: u64,
+}
+
+impl fmt::Display for Extinction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "population reached 0 at generation {} (peak of {} at generation {})",
+            self.generation, self.peak, self.peak_generation
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtinctionDetector {
+    peak: usize,
+    peak_generation: u64,
+}
+
+impl ExtinctionDetector {
+    pub fn new() -> ExtinctionDetector {
+        ExtinctionDetector::default()
+    }
+
+    pub fn peak(&self) -> (usize, u64) {
+        // Largest population so far, and the generation it was seen in
+        (self.peak, self.peak_generation)
+    }
+
+    pub fn record(&mut self, generation: u64, population: usize) -> Option<Extinction> {
+        // Notes the population at `generation`, returning the extinction
+        // if no cells are left
+        if population > self.peak {
+            self.peak = population;
+            self.peak_generation = generation;
+        }
+        if population > 0 {
+            return None;
+        }
+        Some(Extinction {
+            generation,
+            peak: self.peak,
+            peak_generation: self.peak_generation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{board::Board, life::Life};
+
+    fn run(cells: &[(i64, i64)], generations: u64) -> Option<Extinction> {
+        // Ticks a small board with the given cells alive, returning the
+        // first extinction seen
+        let mut board = Board::with_size(6, 6);
+        for &coords in cells {
+            board.set_alive_at(coords, true);
+        }
+        let mut detector = ExtinctionDetector::new();
+        detector.record(0, board.population());
+        for generation in 1..=generations {
+            board.tick();
+            if let Some(extinction) = detector.record(generation, board.population()) {
+                return Some(extinction);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn extinction_lone_cell() {
+        let extinction = run(&[(3, 3)], 10).unwrap();
+        assert_eq!(
+            extinction,
+            Extinction {
+                generation: 1,
+                peak: 1,
+                peak_generation: 0
+            }
+        );
+        assert_eq!(
+            extinction.to_string(),
+            "population reached 0 at generation 1 (peak of 1 at generation 0)"
+        );
+    }
+
+    #[test]
+    fn extinction_blinker() {
+        assert_eq!(run(&[(2, 3), (3, 3), (4, 3)], 100), None);
+    }
+
+    #[test]
+    fn extinction_peak() {
+        let mut detector = ExtinctionDetector::new();
+        for (generation, &population) in [3, 7, 7, 2].iter().enumerate() {
+            assert_eq!(detector.record(generation as u64, population), None);
+        }
+        assert_eq!(detector.peak(), (7, 1));
+        assert_eq!(
+            detector.record(4, 0),
+            Some(Extinction {
+                generation: 4,
+                peak: 7,
+                peak_generation: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,335 @@
+// Bounded board for "Generations" rules, such as Brian's Brain, where
+// a cell that dies spends a few generations dying before it's dead.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    board::{ALIVE_GLYPH, DEAD_GLYPH},
+    error::GolError,
+    life::Life,
+    rule::Rule,
+};
+
+// Characters for dying cells, from just died to nearly dead. Each
+// dying state takes the one its share of the way along.
+const DYING_GLYPHS: [char; 2] = ['▓', '▒'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenerationsRule {
+    // Births and survivals, both counting only alive neighbours
+    rule: Rule,
+    // Number of states a cell can be in: dead, alive, and states - 2
+    // dying ones. 2 is an ordinary life-like rule.
+    states: u8,
+}
+
+impl GenerationsRule {
+    // B2/S/C3, where every alive cell dies after one generation
+    pub const BRIANS_BRAIN: GenerationsRule = GenerationsRule {
+        rule: Rule::SEEDS,
+        states: 3,
+    };
+
+    pub fn new(rule: Rule, states: u8) -> Result<GenerationsRule, GolError> {
+        if states < 2 {
+            return Err(GolError::InvalidRule(format!(
+                "a cell needs at least 2 states, not {}",
+                states
+            )));
+        }
+        Ok(GenerationsRule { rule, states })
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    pub fn next_state(&self, state: u8, num_adjacent_alive: u32) -> u8 {
+        // State of a cell next generation. Dying cells carry on dying
+        // whatever their neighbours, and only dead ones can be born.
+        match state {
+            0 => self.rule.next_state(false, num_adjacent_alive) as u8,
+            1 if self.rule.next_state(true, num_adjacent_alive) => 1,
+            _ if state + 1 < self.states => state + 1,
+            _ => 0,
+        }
+    }
+}
+
+impl FromStr for GenerationsRule {
+    type Err = GolError;
+
+    fn from_str(s: &str) -> Result<GenerationsRule, GolError> {
+        // Parses a rulestring such as "C3/S/B2", with the number of
+        // states after a C, in any order and either case. Brian's
+        // Brain is "C3/S/B2", or "B2/S/C3".
+
+        let invalid =
+            || GolError::InvalidRule(format!("expected a rule like C3/S/B2, found {:?}", s));
+        let mut states = None;
+        let mut rest = Vec::new();
+        for part in s.trim().split('/') {
+            match part.strip_prefix('C').or_else(|| part.strip_prefix('c')) {
+                Some(count) if states.is_none() => {
+                    states = Some(count.parse::<u8>().map_err(|_| invalid())?)
+                }
+                Some(_) => return Err(invalid()),
+                None => rest.push(part),
+            }
+        }
+        let states = states.ok_or_else(invalid)?;
+        GenerationsRule::new(rest.join("/").parse()?, states)
+    }
+}
+
+impl fmt::Display for GenerationsRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/C{}", self.rule, self.states)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationsBoard {
+    width: usize,
+    height: usize,
+    rule: GenerationsRule,
+    // State of each cell, row by row from the top-left: 0 for dead, 1
+    // for alive, and 2 up to the rule's states - 1 for dying
+    cells: Vec<u8>,
+    // Same shape as `cells`, written by each tick and then swapped in
+    next: Vec<u8>,
+}
+
+impl GenerationsBoard {
+    pub fn new(width: usize, height: usize, rule: GenerationsRule) -> GenerationsBoard {
+        GenerationsBoard {
+            width,
+            height,
+            rule,
+            cells: vec![0; width * height],
+            next: vec![0; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn rule(&self) -> GenerationsRule {
+        self.rule
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        // State of the cell at zero-based (x, y)
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, state: u8) {
+        // Sets the cell at zero-based (x, y). States the rule doesn't
+        // have are taken as the last dying one.
+        self.cells[y * self.width + x] = state.min(self.rule.states - 1);
+    }
+
+    pub fn count_adjacent_alive(&self, x: usize, y: usize) -> u32 {
+        // Number of alive neighbours of zero-based (x, y). Dying cells
+        // don't count, and cells beyond the edges are dead.
+        let mut num_adjacent_alive = 0;
+        for ny in y.saturating_sub(1)..=(y + 1).min(self.height - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(self.width - 1) {
+                if (nx, ny) != (x, y) && self.get(nx, ny) == 1 {
+                    num_adjacent_alive += 1;
+                }
+            }
+        }
+        num_adjacent_alive
+    }
+
+    pub fn tick(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.next[y * self.width + x] = self
+                    .rule
+                    .next_state(self.get(x, y), self.count_adjacent_alive(x, y));
+            }
+        }
+        std::mem::swap(&mut self.cells, &mut self.next);
+    }
+
+    pub fn glyph(&self, state: u8) -> char {
+        // Character a cell in `state` is drawn with, shading from alive
+        // through the dying states to dead
+        match state {
+            0 => DEAD_GLYPH,
+            1 => ALIVE_GLYPH,
+            _ => {
+                let dying = (self.rule.states - 2) as usize;
+                DYING_GLYPHS[(state - 2) as usize * DYING_GLYPHS.len() / dying]
+            }
+        }
+    }
+
+    fn index(&self, coords: (i64, i64)) -> Option<(usize, usize)> {
+        // Converts Life coordinates into zero-based (x, y),
+        // or None if they are off the board
+        if coords.0 < 1
+            || coords.1 < 1
+            || coords.0 > self.width as i64
+            || coords.1 > self.height as i64
+        {
+            None
+        } else {
+            Some(((coords.0 - 1) as usize, (coords.1 - 1) as usize))
+        }
+    }
+}
+
+impl fmt::Display for GenerationsBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            for &state in row {
+                write!(f, "{}", self.glyph(state))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Life for GenerationsBoard {
+    // Coordinates run from 1 to width/height inclusive, matching the
+    // dense board. Dying cells aren't alive, and set_alive_at(.., false)
+    // kills a cell outright rather than setting it dying.
+
+    fn step(&mut self) {
+        self.tick();
+    }
+
+    fn is_alive_at(&self, coords: (i64, i64)) -> bool {
+        match self.index(coords) {
+            Some((x, y)) => self.get(x, y) == 1,
+            None => false,
+        }
+    }
+
+    fn set_alive_at(&mut self, coords: (i64, i64), alive: bool) {
+        if let Some((x, y)) = self.index(coords) {
+            self.set(x, y, alive as u8);
+        }
+    }
+
+    fn population(&self) -> usize {
+        self.cells.iter().filter(|&&state| state == 1).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::life::randomize;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn generations_parse() {
+        let brain: GenerationsRule = "C3/S/B2".parse().unwrap();
+        assert_eq!(brain, GenerationsRule::BRIANS_BRAIN);
+        assert_eq!("b2/s/c3".parse::<GenerationsRule>().unwrap(), brain);
+        assert_eq!(brain.to_string(), "B2/S/C3");
+        assert_eq!(
+            "C2/S23/B3".parse::<GenerationsRule>().unwrap().rule(),
+            Rule::CONWAY
+        );
+
+        for invalid in [
+            "",
+            "B2/S",
+            "C1/S/B2",
+            "C3/C3/S/B2",
+            "C/S/B2",
+            "C256/S/B2",
+            "C3/S/B9",
+        ]
+        .iter()
+        {
+            assert!(
+                invalid.parse::<GenerationsRule>().is_err(),
+                "{:?} parsed",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn generations_brians_brain() {
+        // Two alive cells side by side die at once, setting off a
+        // pair of sparks up and down and another left and right
+        let mut board = GenerationsBoard::new(6, 7, GenerationsRule::BRIANS_BRAIN);
+        board.set_alive_at((3, 4), true);
+        board.set_alive_at((4, 4), true);
+
+        board.tick();
+        assert_eq!(
+            board.to_string(),
+            "░░░░░░\n░░░░░░\n░░██░░\n░░▓▓░░\n░░██░░\n░░░░░░\n░░░░░░\n"
+        );
+        board.tick();
+        assert_eq!(
+            board.to_string(),
+            "░░░░░░\n░░██░░\n░░▓▓░░\n░█░░█░\n░░▓▓░░\n░░██░░\n░░░░░░\n"
+        );
+        assert_eq!(board.population(), 6);
+    }
+
+    #[test]
+    fn generations_two_states_match_board() {
+        // With 2 states nothing is ever dying, so it's the plain rule
+        let rule = GenerationsRule::new(Rule::CONWAY, 2).unwrap();
+        let mut generations = GenerationsBoard::new(30, 20, rule);
+        let mut board = Board::with_size(30, 20);
+        randomize(
+            &mut generations,
+            (1, 1),
+            30,
+            20,
+            0.4,
+            &mut StdRng::seed_from_u64(3),
+        );
+        randomize(
+            &mut board,
+            (1, 1),
+            30,
+            20,
+            0.4,
+            &mut StdRng::seed_from_u64(3),
+        );
+        for _ in 0..20 {
+            generations.tick();
+            board.tick();
+            for x in 1..=30 {
+                for y in 1..=20 {
+                    assert_eq!(generations.is_alive_at((x, y)), board.is_alive_at((x, y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generations_dying_glyphs() {
+        let rule = "B2/S/C6".parse().unwrap();
+        let mut board = GenerationsBoard::new(6, 1, rule);
+        for state in 0..6 {
+            board.set(state as usize, 0, state);
+        }
+        assert_eq!(board.to_string(), "░█▓▓▒▒\n");
+        board.set(0, 0, 200);
+        assert_eq!(board.get(0, 0), 5);
+    }
+}
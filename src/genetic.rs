@@ -0,0 +1,174 @@
+// Genetic search: breeding starting boards that score well on a
+// fitness function, such as how many cells are alive after a while.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::board::Board;
+
+// Width and height of the boards bred unless set_size says otherwise
+const GENETIC_BOARD_SIZE: usize = 16;
+
+// Chance of each cell of the first boards starting alive
+const GENETIC_DENSITY: f64 = 0.5;
+
+// Number of boards picked at random for each tournament, the fittest
+// of which becomes a parent
+const TOURNAMENT_SIZE: usize = 3;
+
+pub struct GeneticSearch {
+    // Number of boards in each genetic generation
+    pub population_size: usize,
+    // Chance of each cell of a child being flipped
+    pub mutation_rate: f64,
+    // Higher is fitter. Called with a starting board, so anything
+    // that depends on how it evolves should tick a copy.
+    pub fitness: Box<dyn Fn(&Board) -> f64>,
+    size: (usize, usize),
+    rng: StdRng,
+}
+
+impl GeneticSearch {
+    pub fn new<F>(population_size: usize, mutation_rate: f64, fitness: F) -> GeneticSearch
+    where
+        F: Fn(&Board) -> f64 + 'static,
+    {
+        GeneticSearch {
+            population_size,
+            mutation_rate,
+            fitness: Box::new(fitness),
+            size: (GENETIC_BOARD_SIZE, GENETIC_BOARD_SIZE),
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.size = (width, height);
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        // Searches with the same seed and settings breed the same boards
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn run(&mut self, generations: usize) -> Board {
+        // Starts from population_size random boards and breeds them for
+        // `generations` genetic generations, returning the fittest board
+        // seen along the way. Each child's parents are the winners of
+        // two tournaments, its columns are split between them at a
+        // random column, and then each of its cells may be flipped.
+        assert!(
+            self.population_size > 0,
+            "a genetic search needs a population"
+        );
+        assert!(
+            (0.0..=1.0).contains(&self.mutation_rate),
+            "mutation rate {} is not between 0 and 1",
+            self.mutation_rate
+        );
+
+        let mut population: Vec<Board> = (0..self.population_size)
+            .map(|_| {
+                let mut board = Board::with_size(self.size.0, self.size.1);
+                board.set_history_depth(0);
+                board.reset_random(self.rng.gen(), GENETIC_DENSITY);
+                board
+            })
+            .collect();
+        let mut best: Option<(Board, f64)> = None;
+
+        for generation in 0..=generations {
+            let scores: Vec<f64> = population
+                .iter()
+                .map(|board| (self.fitness)(board))
+                .collect();
+            for (board, &score) in population.iter().zip(&scores) {
+                if best.as_ref().is_none_or(|&(_, best)| score > best) {
+                    best = Some((board.clone(), score));
+                }
+            }
+            if generation == generations {
+                break;
+            }
+            population = (0..self.population_size)
+                .map(|_| {
+                    let first = self.tournament(&scores);
+                    let second = self.tournament(&scores);
+                    let mut child = self.crossover(&population[first], &population[second]);
+                    self.mutate(&mut child);
+                    child
+                })
+                .collect();
+        }
+        best.map(|(board, _)| board).unwrap()
+    }
+
+    fn tournament(&mut self, scores: &[f64]) -> usize {
+        // Index of the fittest of TOURNAMENT_SIZE boards picked at random
+        (0..TOURNAMENT_SIZE)
+            .map(|_| self.rng.gen_range(0..scores.len()))
+            .max_by(|&a, &b| scores[a].total_cmp(&scores[b]))
+            .unwrap()
+    }
+
+    fn crossover(&mut self, first: &Board, second: &Board) -> Board {
+        // A board with the columns left of a random split from `first`
+        // and the rest from `second`
+        let split = self.rng.gen_range(1..=self.size.0);
+        let mut child = Board::with_size(self.size.0, self.size.1);
+        child.set_history_depth(0);
+        child.for_each_mut(|coords, _| {
+            let parent = if coords.0 < split { first } else { second };
+            parent.get_cell(coords).unwrap().alive
+        });
+        child
+    }
+
+    fn mutate(&mut self, board: &mut Board) {
+        let (rng, rate) = (&mut self.rng, self.mutation_rate);
+        board.for_each_mut(|_, alive| alive != rng.gen_bool(rate));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::life::Life;
+
+    fn population_after(ticks: u64) -> impl Fn(&Board) -> f64 {
+        move |board| {
+            let mut board = board.clone();
+            board.tick_n(ticks);
+            board.population() as f64
+        }
+    }
+
+    #[test]
+    fn genetic_search_improves() {
+        let mut search = GeneticSearch::new(12, 0.02, population_after(0));
+        search.set_size(8, 8);
+        search.set_seed(4);
+        let best = search.run(30);
+        assert_eq!((best.width(), best.height()), (8, 8));
+
+        // Fitter than any of the random boards it started from
+        let mut start = GeneticSearch::new(12, 0.02, population_after(0));
+        start.set_size(8, 8);
+        start.set_seed(4);
+        let first = start.run(0);
+        assert!(best.population() > first.population());
+    }
+
+    #[test]
+    fn genetic_search_reproducible() {
+        let run = |generations| {
+            let mut search = GeneticSearch::new(8, 0.05, population_after(20));
+            search.set_size(10, 10);
+            search.set_seed(1);
+            search.run(generations)
+        };
+        assert_eq!(run(5), run(5));
+        // Never worse than the best board it started with
+        let fitness = population_after(20);
+        assert!(fitness(&run(5)) >= fitness(&run(0)));
+    }
+}
@@ -0,0 +1,585 @@
+// Unbounded engine using Gosper's HashLife algorithm. The universe is a
+// quadtree whose identical squares are only stored once, and the future
+// of each square is remembered, so patterns that repeat themselves in
+// space or time can be jumped very far ahead.
+
+use std::collections::HashMap;
+
+use crate::{board::Board, life::Life, rule::Rule};
+
+type NodeId = u32;
+
+// The two single-cell nodes, which every other node is built from
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+// Which of the nine squares in successor surround each quadrant of the
+// centre, in the order nw, ne, sw, se
+const CENTRE_CORNERS: [[usize; 4]; 4] = [[0, 1, 3, 4], [1, 2, 4, 5], [3, 4, 6, 7], [4, 5, 7, 8]];
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    // Quadrants in the order nw, ne, sw, se. Unused by single cells.
+    children: [NodeId; 4],
+    // The node is a square of 2^level cells on each side
+    level: u8,
+    population: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct HashLifeBoard {
+    nodes: Vec<Node>,
+    // Finds the existing node for a set of quadrants, so that equal
+    // squares always share an id
+    ids: HashMap<[NodeId; 4], NodeId>,
+    // Memoized results of successor, keyed by node and step
+    results: HashMap<(NodeId, u8), NodeId>,
+    // The empty node at each level, built as they are needed
+    empty: Vec<NodeId>,
+    root: NodeId,
+    // Stable coordinates of the root's top-left cell. Wider than the
+    // coordinates of the cells themselves, since after a long enough
+    // jump the root can be more than 2^64 cells across.
+    origin: (i128, i128),
+    generation: u64,
+}
+
+impl HashLifeBoard {
+    pub fn new() -> HashLifeBoard {
+        let leaf = |population| Node {
+            children: [DEAD; 4],
+            level: 0,
+            population,
+        };
+        let mut life = HashLifeBoard {
+            nodes: vec![leaf(0), leaf(1)],
+            ids: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            origin: (-4, -4),
+            generation: 0,
+        };
+        life.root = life.empty_node(3);
+        life
+    }
+
+    pub fn from_board(board: &Board) -> HashLifeBoard {
+        // Copies the alive cells of a dense board, keeping their
+        // stable (Life trait) coordinates
+        let mut life = HashLifeBoard::new();
+        let offset = board.offset();
+        for x in 1..=board.width() {
+            for y in 1..=board.height() {
+                if board.get_cell((x, y)).is_some_and(|cell| cell.alive) {
+                    life.set_alive_at((x as i64 + offset.0, y as i64 + offset.1), true);
+                }
+            }
+        }
+        life
+    }
+
+    pub fn to_board(&self, origin: (i64, i64), width: usize, height: usize) -> Board {
+        // Copies the `width` x `height` cells starting at `origin`
+        // onto a new dense board, with `origin` as its cell (1, 1)
+        let mut board = Board::with_size(width, height);
+        for x in 1..=width {
+            for y in 1..=height {
+                if self.is_alive_at((origin.0 + x as i64 - 1, origin.1 + y as i64 - 1)) {
+                    board.get_cell_mut((x, y)).unwrap().alive = true;
+                }
+            }
+        }
+        board
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn advance(&mut self, generations: u64) {
+        // Moves the universe `generations` ahead, one jump for each
+        // power of two they are made up of. Panics if that would take
+        // the generation, counted in a u64, past u64::MAX, before moving
+        // anything.
+        self.check_generations(generations);
+        for j in 0..64 {
+            if generations >> j & 1 == 1 {
+                self.jump(j);
+            }
+        }
+    }
+
+    pub fn step_by_power_of_2(&mut self, log2_steps: u32) {
+        // Moves the universe 2^log2_steps generations ahead in a single
+        // jump. Panics unless log2_steps is below 64, or if the jump
+        // would take the generation past u64::MAX, since the generation
+        // is counted in a u64.
+        assert!(log2_steps < 64, "can't jump 2^{} generations", log2_steps);
+        self.check_generations(1 << log2_steps);
+        self.jump(log2_steps as u8);
+    }
+
+    fn check_generations(&self, generations: u64) {
+        // Panics unless `generations` more still fit in the counter
+        assert!(
+            self.generation.checked_add(generations).is_some(),
+            "can't advance {} generations from generation {}",
+            generations,
+            self.generation
+        );
+    }
+
+    fn jump(&mut self, j: u8) {
+        // Advances by 2^j generations. A node's successor only covers its
+        // centre half, so the root is first padded with empty space until
+        // the pattern can't grow out of that centre in the time.
+
+        loop {
+            let root = self.nodes[self.root as usize];
+            if root.level >= j + 3 && self.inner_population(self.root) == root.population {
+                break;
+            }
+            self.expand();
+        }
+        self.expand();
+
+        let level = self.nodes[self.root as usize].level;
+        self.root = self.successor(self.root, j);
+        let shift = 1i128 << (level - 2);
+        self.origin = (self.origin.0 + shift, self.origin.1 + shift);
+        self.generation += 1 << j;
+    }
+
+    fn successor(&mut self, id: NodeId, j: u8) -> NodeId {
+        // The centre half of node `id`, advanced 2^j generations. Nodes
+        // can look at most 2^(level - 2) generations ahead, so larger
+        // steps are cut down to that.
+
+        let node = self.nodes[id as usize];
+        if node.population == 0 {
+            return self.empty_node(node.level - 1);
+        }
+        let j = j.min(node.level - 2);
+        if let Some(&result) = self.results.get(&(id, j)) {
+            return result;
+        }
+
+        let result = if node.level == 2 {
+            self.step_4x4(id)
+        } else {
+            // Nine overlapping squares, each half the size of this node,
+            // covering it in a 3x3 grid
+            let [a, b, c, d] = node.children;
+            let [_, ab, ac, ad] = self.children(a);
+            let [ba, _, bc, bd] = self.children(b);
+            let [ca, cb, _, cd] = self.children(c);
+            let [da, db, dc, _] = self.children(d);
+            let squares = [
+                a,
+                self.join([ab, ba, ad, bc]),
+                b,
+                self.join([ac, ad, ca, cb]),
+                self.join([ad, bc, cb, da]),
+                self.join([bc, bd, da, db]),
+                c,
+                self.join([cb, da, cd, dc]),
+                d,
+            ];
+            let mut s = [DEAD; 9];
+            for (s, &square) in s.iter_mut().zip(squares.iter()) {
+                *s = self.successor(square, j);
+            }
+
+            let mut quadrants = [DEAD; 4];
+            for (quadrant, corners) in quadrants.iter_mut().zip(CENTRE_CORNERS.iter()) {
+                let [nw, ne, sw, se] = corners.map(|i| s[i]);
+                *quadrant = if j < node.level - 2 {
+                    // The results are already far enough ahead, so the
+                    // centre is pieced together from their inner quadrants
+                    self.join([
+                        self.children(nw)[3],
+                        self.children(ne)[2],
+                        self.children(sw)[1],
+                        self.children(se)[0],
+                    ])
+                } else {
+                    // Full speed: a second step from four of the results
+                    let square = self.join([nw, ne, sw, se]);
+                    self.successor(square, j)
+                };
+            }
+            self.join(quadrants)
+        };
+        self.results.insert((id, j), result);
+        result
+    }
+
+    fn step_4x4(&mut self, id: NodeId) -> NodeId {
+        // The centre 2x2 cells of a 4x4 node, one generation on
+        let rule = Rule::CONWAY;
+        let mut centre = [DEAD; 4];
+        for (i, cell) in centre.iter_mut().enumerate() {
+            let (x, y) = (1 + i as u128 % 2, 1 + i as u128 / 2);
+            let mut num_adjacent_alive = 0;
+            for nx in x - 1..=x + 1 {
+                for ny in y - 1..=y + 1 {
+                    if (nx, ny) != (x, y) && self.get(id, nx, ny) {
+                        num_adjacent_alive += 1;
+                    }
+                }
+            }
+            if rule.next_state(self.get(id, x, y), num_adjacent_alive) {
+                *cell = ALIVE;
+            }
+        }
+        self.join(centre)
+    }
+
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        // The node with these quadrants, creating it if it's new
+        if let Some(&id) = self.ids.get(&children) {
+            return id;
+        }
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            children,
+            level: self.nodes[children[0] as usize].level + 1,
+            population: children
+                .iter()
+                .map(|&child| self.nodes[child as usize].population)
+                .sum(),
+        });
+        self.ids.insert(children, id);
+        id
+    }
+
+    fn children(&self, id: NodeId) -> [NodeId; 4] {
+        self.nodes[id as usize].children
+    }
+
+    fn empty_node(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let below = *self.empty.last().unwrap();
+            let id = self.join([below; 4]);
+            self.empty.push(id);
+        }
+        self.empty[level as usize]
+    }
+
+    fn inner_population(&self, id: NodeId) -> u64 {
+        // Population of the centre half of a node
+        let [a, b, c, d] = self.children(id);
+        [(a, 3), (b, 2), (c, 1), (d, 0)]
+            .iter()
+            .map(|&(child, i)| self.nodes[self.children(child)[i] as usize].population)
+            .sum()
+    }
+
+    fn expand(&mut self) {
+        // Doubles the size of the root, keeping it in the middle
+        let level = self.nodes[self.root as usize].level;
+        let e = self.empty_node(level - 1);
+        let [a, b, c, d] = self.children(self.root);
+        let quadrants = [
+            self.join([e, e, e, a]),
+            self.join([e, e, b, e]),
+            self.join([e, c, e, e]),
+            self.join([d, e, e, e]),
+        ];
+        self.root = self.join(quadrants);
+        let shift = 1i128 << (level - 1);
+        self.origin = (self.origin.0 - shift, self.origin.1 - shift);
+    }
+
+    fn get(&self, mut id: NodeId, mut x: u128, mut y: u128) -> bool {
+        // Whether the cell at (x, y) inside node `id` is alive
+        loop {
+            let node = self.nodes[id as usize];
+            if node.level == 0 {
+                return id == ALIVE;
+            }
+            let half = 1u128 << (node.level - 1);
+            id = node.children[(x >= half) as usize + 2 * (y >= half) as usize];
+            x %= half;
+            y %= half;
+        }
+    }
+
+    fn set(&mut self, id: NodeId, x: u128, y: u128, alive: bool) -> NodeId {
+        // Node `id` with the cell at (x, y) changed
+        let node = self.nodes[id as usize];
+        if node.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+        let half = 1u128 << (node.level - 1);
+        let i = (x >= half) as usize + 2 * (y >= half) as usize;
+        let mut children = node.children;
+        children[i] = self.set(children[i], x % half, y % half, alive);
+        self.join(children)
+    }
+
+    fn root_coords(&self, coords: (i64, i64)) -> Option<(u128, u128)> {
+        // Converts stable coordinates into ones relative to the root,
+        // or None if they are outside it
+        let size = 1i128 << self.nodes[self.root as usize].level;
+        let (x, y) = (
+            coords.0 as i128 - self.origin.0,
+            coords.1 as i128 - self.origin.1,
+        );
+        if (0..size).contains(&x) && (0..size).contains(&y) {
+            Some((x as u128, y as u128))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for HashLifeBoard {
+    fn default() -> HashLifeBoard {
+        HashLifeBoard::new()
+    }
+}
+
+impl Life for HashLifeBoard {
+    fn step(&mut self) {
+        self.advance(1);
+    }
+
+    fn is_alive_at(&self, coords: (i64, i64)) -> bool {
+        match self.root_coords(coords) {
+            Some((x, y)) => self.get(self.root, x, y),
+            None => false,
+        }
+    }
+
+    fn set_alive_at(&mut self, coords: (i64, i64), alive: bool) {
+        // Cells outside the root are already dead
+        if !alive && self.root_coords(coords).is_none() {
+            return;
+        }
+        loop {
+            if let Some((x, y)) = self.root_coords(coords) {
+                self.root = self.set(self.root, x, y, alive);
+                return;
+            }
+            self.expand();
+        }
+    }
+
+    fn population(&self) -> usize {
+        self.nodes[self.root as usize].population as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::life::randomize;
+    use crate::sparse::SparseBoard;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[rustfmt::skip]
+    const GOSPER_GUN: &[(i64, i64)] = &[
+        (1, 5), (1, 6), (2, 5), (2, 6), (11, 5), (11, 6), (11, 7), (12, 4), (12, 8),
+        (13, 3), (13, 9), (14, 3), (14, 9), (15, 6), (16, 4), (16, 8), (17, 5), (17, 6),
+        (17, 7), (18, 6), (21, 3), (21, 4), (21, 5), (22, 3), (22, 4), (22, 5), (23, 2),
+        (23, 6), (25, 1), (25, 2), (25, 6), (25, 7), (35, 3), (35, 4), (36, 3), (36, 4),
+    ];
+
+    fn assert_same_cells(
+        hashlife: &HashLifeBoard,
+        sparse: &SparseBoard,
+        origin: (i64, i64),
+        size: i64,
+    ) {
+        for x in origin.0..origin.0 + size {
+            for y in origin.1..origin.1 + size {
+                assert_eq!(
+                    hashlife.is_alive_at((x, y)),
+                    sparse.is_alive_at((x, y)),
+                    "cell ({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hashlife_matches_sparse() {
+        let r_pentomino = [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)];
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        for pattern in [&r_pentomino[..], &glider[..]].iter() {
+            let mut hashlife = HashLifeBoard::new();
+            let mut sparse = SparseBoard::new();
+            for &coords in pattern.iter() {
+                hashlife.set_alive_at(coords, true);
+                sparse.set_alive_at(coords, true);
+            }
+
+            for _ in 0..100 {
+                hashlife.step();
+                sparse.step();
+                assert_eq!(hashlife.population(), sparse.population());
+            }
+            assert_eq!(hashlife.generation(), 100);
+            assert_same_cells(&hashlife, &sparse, (-60, -60), 160);
+        }
+    }
+
+    #[test]
+    fn hashlife_jump_matches_sparse() {
+        // A random soup advanced 100 generations in a single call
+        let mut hashlife = HashLifeBoard::new();
+        let mut sparse = SparseBoard::new();
+        randomize(
+            &mut hashlife,
+            (-8, -8),
+            16,
+            16,
+            0.5,
+            &mut StdRng::seed_from_u64(3),
+        );
+        randomize(
+            &mut sparse,
+            (-8, -8),
+            16,
+            16,
+            0.5,
+            &mut StdRng::seed_from_u64(3),
+        );
+
+        hashlife.advance(100);
+        for _ in 0..100 {
+            sparse.step();
+        }
+        assert_eq!(hashlife.population(), sparse.population());
+        assert_same_cells(&hashlife, &sparse, (-80, -80), 160);
+    }
+
+    #[test]
+    fn hashlife_gosper_gun() {
+        let mut hashlife = HashLifeBoard::new();
+        for &coords in GOSPER_GUN {
+            hashlife.set_alive_at(coords, true);
+        }
+
+        // Every 30 generations the gun returns to its 36 cells
+        // and has fired one more five-cell glider
+        hashlife.advance(9_990);
+        assert_eq!(hashlife.population(), 36 + 5 * 333);
+        hashlife.advance(10);
+        assert_eq!(hashlife.generation(), 10_000);
+
+        let mut sparse = SparseBoard::new();
+        for &coords in GOSPER_GUN {
+            sparse.set_alive_at(coords, true);
+        }
+        for _ in 0..10 {
+            sparse.step();
+        }
+        assert_eq!(hashlife.population(), sparse.population() + 5 * 333);
+    }
+
+    #[test]
+    fn hashlife_power_of_2() {
+        let mut hashlife = HashLifeBoard::new();
+        for &coords in GOSPER_GUN {
+            hashlife.set_alive_at(coords, true);
+        }
+
+        // 2^30 is 4 more than a multiple of the gun's period of 30
+        hashlife.step_by_power_of_2(30);
+        assert_eq!(hashlife.generation(), 1 << 30);
+        let mut sparse = SparseBoard::new();
+        for &coords in GOSPER_GUN {
+            sparse.set_alive_at(coords, true);
+        }
+        for _ in 0..4 {
+            sparse.step();
+        }
+        let gliders = ((1 << 30) - 4) / 30;
+        assert_eq!(hashlife.population(), sparse.population() + 5 * gliders);
+    }
+
+    #[test]
+    fn hashlife_largest_jumps() {
+        // A blinker has period 2, so any even jump leaves it as it was
+        let blinker = [(0, 1), (1, 1), (2, 1)];
+        for &log2_steps in &[62, 63] {
+            let mut hashlife = HashLifeBoard::new();
+            for &coords in &blinker {
+                hashlife.set_alive_at(coords, true);
+            }
+            hashlife.step_by_power_of_2(log2_steps);
+            assert_eq!(hashlife.generation(), 1 << log2_steps);
+            assert_eq!(hashlife.population(), 3);
+            for &coords in &blinker {
+                assert!(hashlife.is_alive_at(coords));
+            }
+        }
+
+        // Every jump up to 2^63, ending on an odd generation
+        let mut hashlife = HashLifeBoard::new();
+        for &coords in &blinker {
+            hashlife.set_alive_at(coords, true);
+        }
+        hashlife.advance(u64::MAX);
+        assert_eq!(hashlife.generation(), u64::MAX);
+        assert!((0..3).all(|y| hashlife.is_alive_at((1, y))));
+        assert_eq!(hashlife.population(), 3);
+
+        // A glider moves a cell down and right every 4 generations, so
+        // it ends up 2^61 cells away, near the edge of the coordinates
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut hashlife = HashLifeBoard::new();
+        for &coords in &glider {
+            hashlife.set_alive_at(coords, true);
+        }
+        hashlife.step_by_power_of_2(63);
+        assert_eq!(hashlife.population(), 5);
+        for &(x, y) in &glider {
+            assert!(hashlife.is_alive_at((x + (1 << 61), y + (1 << 61))));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "can't jump 2^64 generations")]
+    fn hashlife_jump_too_far() {
+        HashLifeBoard::new().step_by_power_of_2(64);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't advance 9223372036854775808 generations")]
+    fn hashlife_generation_overflow() {
+        let mut hashlife = HashLifeBoard::new();
+        hashlife.step_by_power_of_2(63);
+        hashlife.step_by_power_of_2(63);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't advance 1 generations from generation 18446744073709551615")]
+    fn hashlife_generation_limit() {
+        // Stopping at u64::MAX is fine, but a step past it isn't
+        let mut hashlife = HashLifeBoard::new();
+        hashlife.set_alive_at((0, 0), true);
+        hashlife.advance(u64::MAX - 1);
+        hashlife.advance(1);
+        assert_eq!(hashlife.generation(), u64::MAX);
+        hashlife.advance(1);
+    }
+
+    #[test]
+    fn hashlife_board_round_trip() {
+        let mut board = Board::new();
+        board.reset_random(5, 0.3);
+        let hashlife = HashLifeBoard::from_board(&board);
+        assert_eq!(hashlife.population(), board.population());
+        let copy = hashlife.to_board((1, 1), board.width(), board.height());
+        assert_eq!(copy, board);
+
+        // Cells nothing was ever placed near
+        assert_eq!(hashlife.to_board((-10, -10), 5, 5).population(), 0);
+    }
+}
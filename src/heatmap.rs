@@ -0,0 +1,93 @@
+// Running count of how often each cell of a dense board has been alive.
+
+use crate::board::Board;
+
+// Shades for cells that have been alive for up to a quarter, half,
+// three quarters and all of the recorded generations
+pub const HEAT_GLYPHS: [char; 4] = ['░', '▒', '▓', '█'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeatMap {
+    // Indexed [x][y] like the board, so column and row 0 are unused
+    pub counts: Vec<Vec<u32>>,
+    // Number of generations recorded
+    generations: u32,
+}
+
+impl HeatMap {
+    pub fn new(width: usize, height: usize) -> HeatMap {
+        HeatMap {
+            counts: vec![vec![0; height + 1]; width + 1],
+            generations: 0,
+        }
+    }
+
+    pub fn generations(&self) -> u32 {
+        self.generations
+    }
+
+    pub fn record(&mut self, board: &Board) {
+        // Counts every alive cell of `board`. Meant to be called once
+        // per tick. Cells beyond the heat map's size are ignored.
+        for (x, column) in self.counts.iter_mut().enumerate().skip(1) {
+            for (y, count) in column.iter_mut().enumerate().skip(1) {
+                if board.get_cell((x, y)).is_some_and(|cell| cell.alive) {
+                    *count += 1;
+                }
+            }
+        }
+        self.generations += 1;
+    }
+
+    pub fn heat(&self, coords: (usize, usize)) -> f64 {
+        // Fraction of the recorded generations the cell was alive for
+        let count = self
+            .counts
+            .get(coords.0)
+            .and_then(|column| column.get(coords.1))
+            .copied()
+            .unwrap_or(0);
+        if self.generations == 0 {
+            0.0
+        } else {
+            count as f64 / self.generations as f64
+        }
+    }
+
+    pub fn glyph(&self, coords: (usize, usize)) -> char {
+        // Shade for a cell, hotter the more often it was alive
+        let heat = self.heat(coords);
+        let level = (heat * HEAT_GLYPHS.len() as f64).ceil() as usize;
+        HEAT_GLYPHS[level.clamp(1, HEAT_GLYPHS.len()) - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heatmap_record() {
+        // Blinker, whose centre is always alive and whose four
+        // arms are each alive half the time
+        let mut board = Board::with_size(5, 5);
+        for coords in [(3, 2), (3, 3), (3, 4)].iter() {
+            board.get_cell_mut(*coords).unwrap().flip();
+        }
+        let mut heat = HeatMap::new(5, 5);
+        for _ in 0..4 {
+            heat.record(&board);
+            board.tick();
+        }
+
+        assert_eq!(heat.generations(), 4);
+        assert_eq!(heat.counts[3][3], 4);
+        assert_eq!(heat.counts[3][2], 2);
+        assert_eq!(heat.counts[2][3], 2);
+        assert_eq!(heat.counts[1][1], 0);
+        assert_eq!(heat.glyph((3, 3)), '█');
+        assert_eq!(heat.glyph((3, 2)), '▒');
+        assert_eq!(heat.glyph((1, 1)), '░');
+        assert_eq!(heat.heat((10, 10)), 0.0);
+    }
+}
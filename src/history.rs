@@ -0,0 +1,159 @@
+// Undo/redo history of board states.
+
+use std::collections::VecDeque;
+
+// Number of generations that can be undone unless configured otherwise.
+pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+// The cells and generation of a dense board at some point, kept by the
+// undo history or by callers of Board::snapshot. Two snapshots are equal
+// when they were taken of the same cells at the same generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardSnapshot {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) generation: u64,
+    pub(crate) offset: (i64, i64),
+    // Whether the cells beyond the board were alive
+    pub(crate) background: bool,
+    // One bit per cell, column by column, lowest bit first
+    pub(crate) cells: Vec<u64>,
+}
+
+impl BoardSnapshot {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        // Width and height of the board when the snapshot was taken
+        (self.width, self.height)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct History {
+    // Oldest first. Once full, recording a new state drops the oldest.
+    past: VecDeque<BoardSnapshot>,
+    // States that were undone, most recently undone last
+    future: Vec<BoardSnapshot>,
+    max_depth: usize,
+}
+
+impl History {
+    pub fn new(max_depth: usize) -> History {
+        History {
+            past: VecDeque::with_capacity(max_depth),
+            future: Vec::new(),
+            max_depth,
+        }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        // Changes how many states are kept, dropping the oldest
+        // ones if there are now too many
+        self.max_depth = max_depth;
+        while self.past.len() > max_depth {
+            self.past.pop_front();
+        }
+        self.future.truncate(max_depth);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    pub fn record(&mut self, snapshot: BoardSnapshot) {
+        // Remembers a state that is about to be left behind.
+        // Anything that was undone can no longer be redone.
+
+        self.future.clear();
+        if self.max_depth == 0 {
+            return;
+        }
+        if self.past.len() == self.max_depth {
+            self.past.pop_front();
+        }
+        self.past.push_back(snapshot);
+    }
+
+    pub fn undo(&mut self, current: BoardSnapshot) -> Option<BoardSnapshot> {
+        // Takes the most recently recorded state, keeping `current`
+        // so it can be redone
+        let previous = self.past.pop_back()?;
+        self.future.push(current);
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: BoardSnapshot) -> Option<BoardSnapshot> {
+        // Takes the most recently undone state, keeping `current`
+        // so it can be undone again
+        let next = self.future.pop()?;
+        self.past.push_back(current);
+        Some(next)
+    }
+}
+
+impl Default for History {
+    fn default() -> History {
+        History::new(DEFAULT_MAX_DEPTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(generation: u64) -> BoardSnapshot {
+        BoardSnapshot {
+            width: 1,
+            height: 1,
+            generation,
+            offset: (0, 0),
+            background: false,
+            cells: vec![0],
+        }
+    }
+
+    #[test]
+    fn history_evicts_oldest() {
+        let mut history = History::new(3);
+        for generation in 0..5 {
+            history.record(snapshot(generation));
+        }
+
+        let mut current = snapshot(5);
+        let mut undone = Vec::new();
+        while let Some(previous) = history.undo(current) {
+            undone.push(previous.generation);
+            current = previous;
+        }
+        assert_eq!(undone, vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn history_record_clears_future() {
+        let mut history = History::new(3);
+        history.record(snapshot(0));
+        history.undo(snapshot(1)).unwrap();
+        assert!(history.can_redo());
+
+        history.record(snapshot(0));
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn history_depth_zero() {
+        let mut history = History::new(0);
+        history.record(snapshot(0));
+        assert!(!history.can_undo());
+    }
+}
@@ -0,0 +1,27 @@
+// Conway's Game of Life, implemented in Rust.
+
+pub mod bitboard;
+pub mod board;
+pub mod boardset;
+pub mod builder;
+// crossterm isn't built for the browser, even with the feature on
+#[cfg(all(feature = "interactive", not(target_arch = "wasm32")))]
+pub mod controls;
+pub mod coord;
+pub mod cycle;
+pub mod error;
+pub mod extinction;
+pub mod generations;
+pub mod genetic;
+pub mod hashlife;
+pub mod heatmap;
+pub mod history;
+pub mod life;
+pub mod pattern;
+pub mod render;
+pub mod rule;
+pub mod soup;
+pub mod sparse;
+pub mod stats;
+#[cfg(feature = "wasm")]
+pub mod wasm;
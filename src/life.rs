@@ -0,0 +1,157 @@
+// Interface shared by the different board implementations, so the
+// renderer and the CLI can drive any of them.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use rand::{
+    distributions::{Bernoulli, Distribution},
+    Rng,
+};
+
+use crate::{
+    board::{ALIVE_GLYPH, DEAD_GLYPH},
+    stats::TickStats,
+};
+
+pub trait Life {
+    // Advances the board by one generation.
+    fn step(&mut self);
+
+    // Whether the cell at the given coordinates is alive.
+    // Cells outside the board are dead, unless a B0 rule has
+    // brought the whole background to life.
+    fn is_alive_at(&self, coords: (i64, i64)) -> bool;
+
+    // Sets the state of the cell at the given coordinates.
+    // Cells outside the board are left untouched.
+    fn set_alive_at(&mut self, coords: (i64, i64), alive: bool);
+
+    // Flips the state of the cell at the given coordinates.
+    fn flip_at(&mut self, coords: (i64, i64)) {
+        let alive = self.is_alive_at(coords);
+        self.set_alive_at(coords, !alive);
+    }
+
+    // Number of alive cells on the board.
+    fn population(&self) -> usize;
+
+    // Advances the board by one generation like step, returning what
+    // changed on boards that keep count.
+    fn step_with_stats(&mut self) -> Option<TickStats> {
+        self.step();
+        None
+    }
+
+    // Goes back to the state before the last generation, if the board
+    // keeps a history. Returns whether there was anything to undo.
+    fn undo(&mut self) -> bool {
+        false
+    }
+
+    // Reapplies the last undone generation, if the board keeps a
+    // history. Returns whether there was anything to redo.
+    fn redo(&mut self) -> bool {
+        false
+    }
+}
+
+pub fn randomize<R: Rng>(
+    life: &mut dyn Life,
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+    density: f64,
+    rng: &mut R,
+) {
+    // Sets every cell in the `width` x `height` rectangle starting at
+    // `origin` alive with probability `density`, visiting the cells
+    // column by column so a given rng seed gives the same soup on
+    // every engine.
+
+    let dist = Bernoulli::new(density).unwrap();
+    for x in origin.0..origin.0 + width {
+        for y in origin.1..origin.1 + height {
+            life.set_alive_at((x, y), dist.sample(rng));
+        }
+    }
+}
+
+pub fn center_of_mass(
+    life: &dyn Life,
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+) -> Option<(f64, f64)> {
+    // Mean coordinates of the alive cells in the `width` x `height`
+    // rectangle starting at `origin`, or None if they are all dead.
+
+    let (mut count, mut sum_x, mut sum_y) = (0, 0, 0);
+    for x in origin.0..origin.0 + width {
+        for y in origin.1..origin.1 + height {
+            if life.is_alive_at((x, y)) {
+                count += 1;
+                sum_x += x;
+                sum_y += y;
+            }
+        }
+    }
+    if count == 0 {
+        None
+    } else {
+        Some((sum_x as f64 / count as f64, sum_y as f64 / count as f64))
+    }
+}
+
+pub fn window_hash(life: &dyn Life, origin: (i64, i64), width: i64, height: i64) -> u64 {
+    // Hash of which cells are alive in the `width` x `height` rectangle
+    // starting at `origin`, for spotting when it repeats on any engine
+
+    let mut hasher = DefaultHasher::new();
+    for x in origin.0..origin.0 + width {
+        for y in origin.1..origin.1 + height {
+            if life.is_alive_at((x, y)) {
+                (x, y).hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+pub fn render(life: &dyn Life, origin: (i64, i64), width: i64, height: i64) -> String {
+    // Draws the `width` x `height` window of the board starting at
+    // `origin`, one line per row, with the same glyphs as `Cell`.
+    render_with_cursor(life, origin, width, height, None)
+}
+
+pub fn render_with_cursor(
+    life: &dyn Life,
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+    cursor: Option<(i64, i64)>,
+) -> String {
+    // Same as `render`, but highlights the cell under `cursor`
+    // using the terminal's reverse video mode.
+
+    let mut out = String::new();
+    for y in origin.1..origin.1 + height {
+        for x in origin.0..origin.0 + width {
+            if cursor == Some((x, y)) {
+                out.push_str("\x1B[7m");
+            }
+            if life.is_alive_at((x, y)) {
+                out.push(ALIVE_GLYPH);
+            } else {
+                out.push(DEAD_GLYPH);
+            }
+            if cursor == Some((x, y)) {
+                out.push_str("\x1B[27m");
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -1,457 +1,1119 @@
 // Conway's Game of Life, implemented in Rust.
 
-use std::{fmt, thread::sleep, time, write};
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant},
+};
 
-use rand::distributions::{Bernoulli, Distribution};
+#[cfg(feature = "interactive")]
+use crossterm::event::{self, Event};
+#[cfg(feature = "interactive")]
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    Terminal,
+};
 
-const BOARD_WIDTH: usize = 125 + 1;
-const BOARD_HEIGHT: usize = 70 + 1;
+use gameoflife_rs::{
+    bitboard::BitBoard,
+    board::{Board, Parallelism, BOARD_HEIGHT, BOARD_WIDTH},
+    builder::BoardBuilder,
+    cycle::{Cycle, CycleDetector},
+    error::{BoardError, GolError, PatternTooLargeError},
+    extinction::{Extinction, ExtinctionDetector},
+    hashlife::HashLifeBoard,
+    life::{randomize, window_hash, Life},
+    pattern::registry::{PatternInfo, PatternRegistry},
+    rule::Rule,
+    sparse::SparseBoard,
+    stats::{entropy, StatsWriter, TickStats},
+};
+#[cfg(feature = "interactive")]
+use gameoflife_rs::{
+    controls::Controls,
+    life::center_of_mass,
+    render::tui::{TerminalGuard, TuiRenderer},
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-struct Cell {
-    alive: bool,
-    x_coord: usize,
-    y_coord: usize,
+// Number of generations captured by `--gif`.
+const GIF_GENERATIONS: usize = 200;
+
+// Number of generations run by `--metrics` unless `--generations` says
+// otherwise.
+const METRICS_GENERATIONS: u64 = 1000;
+
+// Time between generations in the non-interactive terminal view.
+#[cfg(not(feature = "interactive"))]
+const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+const USAGE: &str =
+    "Usage: gameoflife-rs [--engine dense|sparse|bitpacked|hashlife] [--grow <width>x<height>] [--gif <path>] [--threads <n>] [--stop-on-cycle] [--[no-]stop-on-extinction] [--rule <B.../S...>] [--no-status] [--stats-out <path>] [--ascii] [--pattern name:<pattern>] [--metrics <path> [--generations <n>]] [--import-image <path>]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Engine {
+    Dense,
+    Sparse,
+    BitPacked,
+    HashLife,
 }
 
-impl Cell {
-    fn flip(&mut self) {
-        self.alive = !self.alive;
-    }
+#[derive(Debug, PartialEq, Eq)]
+struct Config {
+    engine: Engine,
+    // Largest size the dense board may grow to
+    grow: Option<(usize, usize)>,
+    gif: Option<PathBuf>,
+    // Number of threads the dense board ticks on
+    threads: Option<usize>,
+    // Exit once the board settles into a cycle, rather than
+    // just saying so and running on
+    stop_on_cycle: bool,
+    // Exit once every cell has died. On by default when there's no
+    // one at the keyboard to do anything about it.
+    stop_on_extinction: bool,
+    // Rule the dense board runs, given like B36/S23
+    rule: Rule,
+    // Show the generation, population and rule under the board
+    status: bool,
+    // CSV file to log each generation's population to
+    stats_out: Option<PathBuf>,
+    // Keep to ASCII in the population sparkline, for terminals
+    // without the block characters
+    ascii: bool,
+    // Well-known pattern to start from instead of a random soup
+    pattern: Option<PatternInfo>,
+    // CSV file to log the generations to, running them as fast as
+    // possible without showing the board
+    metrics: Option<PathBuf>,
+    // Number of generations run for `metrics`
+    generations: u64,
+    // PNG or BMP image to start the dense board from instead of a
+    // random soup, with the board and view the size of the image
+    image: Option<PathBuf>,
 }
 
-impl fmt::Display for Cell {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.alive {
-            write!(f, "█")
-        } else {
-            write!(f, "░")
+impl Config {
+    fn from_args<I: Iterator<Item = String>>(mut args: I) -> Result<Config, String> {
+        let mut config = Config {
+            engine: Engine::Dense,
+            grow: None,
+            gif: None,
+            threads: None,
+            stop_on_cycle: false,
+            stop_on_extinction: !cfg!(feature = "interactive"),
+            rule: Rule::CONWAY,
+            status: true,
+            stats_out: None,
+            ascii: false,
+            pattern: None,
+            metrics: None,
+            generations: METRICS_GENERATIONS,
+            image: None,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--engine" => {
+                    config.engine = match args.next().as_deref() {
+                        Some("dense") => Engine::Dense,
+                        Some("sparse") => Engine::Sparse,
+                        Some("bitpacked") => Engine::BitPacked,
+                        Some("hashlife") => Engine::HashLife,
+                        _ => {
+                            return Err(String::from(
+                                "--engine expects dense, sparse, bitpacked or hashlife",
+                            ))
+                        }
+                    }
+                }
+                "--grow" => match args.next().as_deref().and_then(parse_size) {
+                    Some(size) => config.grow = Some(size),
+                    None => return Err(String::from("--grow expects a size like 500x300")),
+                },
+                // Records the simulation to an animated GIF
+                // instead of playing it in the terminal
+                "--gif" => match args.next() {
+                    Some(path) => config.gif = Some(PathBuf::from(path)),
+                    None => return Err(String::from("--gif expects a path")),
+                },
+                "--threads" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => config.threads = Some(n),
+                    _ => return Err(String::from("--threads expects a number above 0")),
+                },
+                "--stop-on-cycle" => config.stop_on_cycle = true,
+                "--stop-on-extinction" => config.stop_on_extinction = true,
+                "--no-stop-on-extinction" => config.stop_on_extinction = false,
+                "--rule" => match args.next().map(|rule| rule.parse()) {
+                    Some(Ok(rule)) => config.rule = rule,
+                    Some(Err(e)) => return Err(format!("--rule: {}", e)),
+                    None => return Err(String::from("--rule expects a rule like B3/S23")),
+                },
+                "--no-status" => config.status = false,
+                "--ascii" => config.ascii = true,
+                "--stats-out" => match args.next() {
+                    Some(path) => config.stats_out = Some(PathBuf::from(path)),
+                    None => return Err(String::from("--stats-out expects a path")),
+                },
+                "--metrics" => match args.next() {
+                    Some(path) => config.metrics = Some(PathBuf::from(path)),
+                    None => return Err(String::from("--metrics expects a path")),
+                },
+                "--generations" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) => config.generations = n,
+                    None => return Err(String::from("--generations expects a number")),
+                },
+                "--pattern" => match args.next().as_deref().and_then(|p| p.strip_prefix("name:")) {
+                    Some(name) => config.pattern = Some(find_pattern(name)?),
+                    None => {
+                        return Err(String::from(
+                            "--pattern expects a pattern's name, like name:glider",
+                        ))
+                    }
+                },
+                "--import-image" => match args.next() {
+                    Some(path) => config.image = Some(PathBuf::from(path)),
+                    None => return Err(String::from("--import-image expects a path")),
+                },
+                _ => return Err(format!("Unknown argument {}", arg)),
+            }
+        }
+        if config.pattern.is_some() && config.image.is_some() {
+            return Err(String::from(
+                "--pattern and --import-image can't be used together",
+            ));
         }
+        Ok(config)
     }
 }
 
-struct Board {
-    board: [[Cell; BOARD_HEIGHT]; BOARD_WIDTH],
+fn find_pattern(name: &str) -> Result<PatternInfo, String> {
+    // Looks up a well-known pattern, suggesting any with similar
+    // names if there's none by that name
+    let registry = PatternRegistry::default();
+    if let Some(pattern) = registry.get(name) {
+        return Ok(pattern.clone());
+    }
+    let similar: Vec<_> = registry.search(name).iter().map(|p| p.name).collect();
+    if similar.is_empty() {
+        Err(format!("--pattern: no pattern called {}", name))
+    } else {
+        Err(format!(
+            "--pattern: no pattern called {}. Did you mean {}?",
+            name,
+            similar.join(", ")
+        ))
+    }
 }
 
-impl Board {
-    fn new() -> Board {
-        let mut board = Board {
-            board: [[Cell {
-                alive: false,
-                x_coord: 0,
-                y_coord: 0,
-            }; BOARD_HEIGHT]; BOARD_WIDTH],
-        };
-        for x in 1..BOARD_WIDTH {
-            for y in 1..BOARD_HEIGHT {
-                let mut cell = board.get_cell_mut((x, y)).unwrap();
-                cell.x_coord = x;
-                cell.y_coord = y;
-            }
+fn parse_size(size: &str) -> Option<(usize, usize)> {
+    // Parses a size written as <width>x<height>
+    let mut parts = size.split('x');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(width), Some(height), None) => Some((width.parse().ok()?, height.parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn main() {
+    let config = match Config::from_args(env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
         }
-        board
+    };
+
+    if config.engine != Engine::Dense
+        && (config.gif.is_some()
+            || config.grow.is_some()
+            || config.threads.is_some()
+            || config.rule != Rule::CONWAY
+            || config.stats_out.is_some()
+            || config.metrics.is_some()
+            || config.image.is_some())
+    {
+        eprintln!(
+            "--gif, --grow, --threads, --rule, --stats-out, --metrics and --import-image are only supported by the dense engine"
+        );
+        process::exit(2);
     }
 
-    fn get_cell(&self, coords: (usize, usize)) -> Option<&Cell> {
-        // Takes a coordinate pair, returns an Option containing
-        // the cell at that coordinate.
-        // If the cell does not exist (example: beyond boundary
-        // of what's allowed on the board), then it contains None
+    let imported = config.image.as_deref().map(import_image);
+    let (width, height) = match &imported {
+        Some(board) => (board.width() as i64, board.height() as i64),
+        None => ((BOARD_WIDTH - 1) as i64, (BOARD_HEIGHT - 1) as i64),
+    };
 
-        if coords.0 == 0 || coords.1 == 0 || coords.0 == BOARD_WIDTH || coords.1 == BOARD_HEIGHT {
-            None
-        } else {
-            match self.board.get(coords.0) {
-                Some(column) => match column.get(coords.1) {
-                    Some(cell) => Some(&cell),
-                    None => None,
-                },
-                None => None,
-            }
+    if let Some(path) = &config.gif {
+        let mut board = dense_board(&config, imported, width, height);
+        if let Err(e) = board.record_gif(GIF_GENERATIONS, 10, path) {
+            eprintln!("Failed to write {}: {}", path.display(), e);
+            process::exit(1);
         }
+        return;
     }
 
-    fn get_cell_mut(&mut self, coords: (usize, usize)) -> Option<&mut Cell> {
-        // Takes a coordinate pair, returns a mutable Option containing
-        // the cell at that coordinate.
-        // If the cell does not exist (example: beyond boundary of the board),
-        // then it contains None
-        if coords.0 == 0 || coords.1 == 0 || coords.0 == BOARD_WIDTH || coords.1 == BOARD_HEIGHT {
-            return Option::None;
+    if let Some(path) = &config.metrics {
+        let mut board = dense_board(&config, imported, width, height);
+        if let Err(e) = write_metrics(&mut board, config.generations, path) {
+            eprintln!("Failed to write {}: {}", path.display(), e);
+            process::exit(1);
         }
+        return;
+    }
 
-        match self.board.get_mut(coords.0) {
-            Some(column) => match column.get_mut(coords.1) {
-                Some(cell) => Some(cell),
-                None => None,
-            },
-            None => None,
+    let mut board: Box<dyn Life> = match config.engine {
+        Engine::Dense => Box::new(dense_board(&config, imported, width, height)),
+        Engine::Sparse => Box::new(SparseBoard::new()),
+        Engine::BitPacked => Box::new(BitBoard::new(width as usize, height as usize)),
+        Engine::HashLife => Box::new(HashLifeBoard::new()),
+    };
+    // The dense board comes out of its builder already seeded
+    if config.engine != Engine::Dense {
+        if let Err(e) = seed(board.as_mut(), &config, width, height) {
+            exit_with_board_error(&e);
         }
     }
 
-    fn count_adjacent_alive(&self, coords: (usize, usize)) -> u32 {
-        let mut num_adjacent_alive = 0;
+    // Failing to log the stats isn't worth stopping the run for
+    let mut stats_out =
+        config
+            .stats_out
+            .as_ref()
+            .and_then(|path| match StatsWriter::create(path) {
+                Ok(writer) => Some(writer),
+                Err(e) => {
+                    eprintln!("Warning: can't write stats to {}: {}", path.display(), e);
+                    None
+                }
+            });
 
-        // For each of the eight adjacent cells,
-        // get the cell with Board::get_cell(coords), then
-        // check if cell is alive. If so, increment counter.
+    #[cfg(feature = "interactive")]
+    let stopped = match run_interactive(board.as_mut(), width, height, &config, &mut stats_out) {
+        Ok(stopped) => stopped,
+        Err(e) => {
+            eprintln!("Terminal error: {}", e);
+            process::exit(1);
+        }
+    };
 
-        // Column to the left of the cell
-        if let Some(cell) = self.get_cell((coords.0 - 1, coords.1 - 1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
-            }
+    #[cfg(not(feature = "interactive"))]
+    let stopped = run(board.as_mut(), width, height, &config, &mut stats_out);
+
+    if let (Some(writer), Some(path)) = (stats_out, &config.stats_out) {
+        if let Err(e) = writer.finish() {
+            eprintln!("Warning: failed writing stats to {}: {}", path.display(), e);
         }
-        if let Some(cell) = self.get_cell((coords.0 - 1, coords.1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
+    }
+
+    match stopped {
+        Some(Stop::Cycle(cycle)) => println!(
+            "The board {}, with a population of {}",
+            cycle,
+            board.population()
+        ),
+        Some(Stop::Extinction(extinction)) => println!("The {}", extinction),
+        None => (),
+    }
+}
+
+fn seed(board: &mut dyn Life, config: &Config, width: i64, height: i64) -> Result<(), BoardError> {
+    // Starts one of the other engines' boards off with the chosen
+    // pattern in the middle of the view, or with randomly alive and dead
+    // cells if there isn't one, the way BoardBuilder does for the dense
+    // board. A pattern too big for the view isn't placed at all.
+    match &config.pattern {
+        Some(pattern) => {
+            let (pattern_width, pattern_height) = pattern.size();
+            if pattern_width as i64 > width || pattern_height as i64 > height {
+                return Err(BoardError::PatternTooLarge(PatternTooLargeError {
+                    pattern: (pattern_width, pattern_height),
+                    board: (width as usize, height as usize),
+                }));
             }
-        }
-        if let Some(cell) = self.get_cell((coords.0 - 1, coords.1 + 1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
+            let left = 1 + (width - pattern_width as i64) / 2;
+            let top = 1 + (height - pattern_height as i64) / 2;
+            for &(x, y) in &pattern.cells {
+                board.set_alive_at((left + x as i64, top + y as i64), true);
             }
         }
-        // Column containing the cell
-        if let Some(cell) = self.get_cell((coords.0, coords.1 - 1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
-            }
+        None => randomize(board, (1, 1), width, height, 0.5, &mut rand::thread_rng()),
+    }
+    Ok(())
+}
+
+fn exit_with_board_error(e: &BoardError) -> ! {
+    // Anything wrong with what the board was asked to do or load comes
+    // from the arguments, so exits with 2 like bad arguments do. A file
+    // that can't be read at all exits with 1.
+    match e {
+        BoardError::PatternTooLarge(_) => eprintln!("The pattern is too big: {}", e),
+        BoardError::OutOfBounds(_) => eprintln!("A cell is off the board: {}", e),
+        BoardError::DimensionMismatch(_) => eprintln!("The boards don't match: {}", e),
+        BoardError::Invalid(_) | BoardError::Corrupt(_) | BoardError::UnsupportedVersion { .. } => {
+            eprintln!("The saved board can't be loaded: {}", e)
         }
-        // Skipping the target cell itself
-        // if let Some(cell) = self.get_cell((coords.0, coords.1)) {
-        //     if cell.alive { num_adjacent_alive += 1;}
-        // }
-        if let Some(cell) = self.get_cell((coords.0, coords.1 + 1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
-            }
+        BoardError::Io(_) => {
+            eprintln!("Couldn't read the saved board: {}", e);
+            process::exit(1);
         }
-        // Column to the right of the cell
-        if let Some(cell) = self.get_cell((coords.0 + 1, coords.1 - 1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
-            }
+    }
+    process::exit(2);
+}
+
+fn write_metrics(board: &mut Board, generations: u64, path: &Path) -> io::Result<()> {
+    // Ticks the board `generations` times, logging each one
+    let mut writer = StatsWriter::create(path)?;
+    for _ in 0..generations {
+        writer.record(&board.tick());
+    }
+    writer.finish()
+}
+
+#[cfg(feature = "image")]
+fn import_image(path: &Path) -> Board {
+    match Board::from_image(path) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "image"))]
+fn import_image(_path: &Path) -> Board {
+    eprintln!("--import-image needs gameoflife-rs built with the image feature");
+    process::exit(2);
+}
+
+fn dense_board(config: &Config, imported: Option<Board>, width: i64, height: i64) -> Board {
+    // Builds the dense board with the options only it supports, seeded
+    // with the chosen pattern or random cells to fill the view. A board
+    // imported from an image is used as it is instead.
+    let mut board = match imported {
+        Some(mut board) => {
+            board.set_grow(config.grow);
+            board.set_rule(config.rule);
+            board
         }
-        if let Some(cell) = self.get_cell((coords.0 + 1, coords.1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
+        None => {
+            let builder = BoardBuilder::new()
+                .size(width as usize, height as usize)
+                .rule(config.rule)
+                .grow(config.grow);
+            let builder = match &config.pattern {
+                Some(pattern) => builder.pattern(&pattern.cells),
+                None => builder.density(0.5),
+            };
+            match builder.build() {
+                Ok(board) => board,
+                Err(GolError::PatternTooLarge(e)) => {
+                    exit_with_board_error(&BoardError::PatternTooLarge(e))
+                }
+                Err(e) => {
+                    eprintln!("Can't set up the board: {}", e);
+                    process::exit(2);
+                }
             }
         }
-        if let Some(cell) = self.get_cell((coords.0 + 1, coords.1 + 1)) {
-            if cell.alive {
-                num_adjacent_alive += 1;
+    };
+    match config.threads {
+        None | Some(1) => (),
+        #[cfg(feature = "parallel")]
+        Some(threads) => {
+            if let Err(e) = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build_global()
+            {
+                eprintln!("Failed to start threads: {}", e);
+                process::exit(1);
             }
+            board.set_parallelism(Parallelism::Rayon);
         }
+        #[cfg(not(feature = "parallel"))]
+        Some(threads) => board.set_parallelism(Parallelism::Strips(Some(threads))),
+    }
+    board
+}
+
+// Why a run of the terminal view ended early
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+    Cycle(Cycle),
+    Extinction(Extinction),
+}
 
-        //Return value
-        num_adjacent_alive
+// Follows the generations shown in the terminal view, watching for
+// the visible cells to start repeating or to die out. Cells out of
+// view are ignored.
+struct Watch {
+    detector: CycleDetector,
+    extinction_detector: ExtinctionDetector,
+    // Number of generations stepped through
+    generation: u64,
+    // The first cycle found since the board was last edited
+    cycle: Option<Cycle>,
+    extinction: Option<Extinction>,
+    // What the last step did, on boards that keep count. Forgotten
+    // when the board is edited.
+    last_tick: Option<TickStats>,
+    width: i64,
+    height: i64,
+}
+
+impl Watch {
+    fn new(board: &dyn Life, width: i64, height: i64) -> Watch {
+        let mut watch = Watch {
+            detector: CycleDetector::default(),
+            extinction_detector: ExtinctionDetector::new(),
+            generation: 0,
+            cycle: None,
+            extinction: None,
+            last_tick: None,
+            width,
+            height,
+        };
+        watch.restart(board);
+        watch
     }
 
-    fn get_cells_to_flip(&self) -> Vec<(usize, usize)> {
-        // Iterates across the whole board, identifying cells
-        // that need to be flipped
+    fn hash(&self, board: &dyn Life) -> u64 {
+        window_hash(board, (1, 1), self.width, self.height)
+    }
 
-        let mut cells_to_flip: Vec<(usize, usize)> = Vec::new();
+    fn step(&mut self, board: &mut dyn Life, stats_out: &mut Option<StatsWriter>) {
+        // Advances the board, logging the generation if asked to
+        self.last_tick = board.step_with_stats();
+        if let (Some(writer), Some(stats)) = (stats_out, &self.last_tick) {
+            writer.record(stats);
+        }
 
-        for column in &self.board[1..BOARD_WIDTH] {
-            for cell in &column[1..BOARD_HEIGHT] {
-                let num_adjacent_alive = self.count_adjacent_alive((cell.x_coord, cell.y_coord));
-                if cell.alive {
-                    match num_adjacent_alive {
-                        2 => (),
-                        3 => (),
-                        _ => cells_to_flip.push((cell.x_coord, cell.y_coord)),
-                    }
-                } else {
-                    match num_adjacent_alive {
-                        3 => cells_to_flip.push((cell.x_coord, cell.y_coord)),
-                        _ => (),
-                    }
-                }
-            }
+        self.generation += 1;
+        if self.cycle.is_none() {
+            self.cycle = self.detector.record(self.generation, self.hash(board));
+        }
+        if self.extinction.is_none() {
+            let population = self.population(board);
+            self.extinction = self.extinction_detector.record(self.generation, population);
         }
-        cells_to_flip
     }
 
-    fn tick(&mut self) {
-        let to_flip = self.get_cells_to_flip();
-        for coords in to_flip.iter() {
-            if let Some(cell) = self.get_cell_mut(*coords) {
-                cell.flip();
+    fn population(&self, board: &dyn Life) -> usize {
+        // Taken from the last step where possible, rather than
+        // counting the board again
+        match self.last_tick {
+            Some(stats) => stats.population_after,
+            None => board.population(),
+        }
+    }
+
+    fn restart(&mut self, board: &dyn Life) {
+        // Starts looking again from the current board, after it has
+        // been edited or stepped through history
+        self.last_tick = None;
+        self.detector.clear();
+        self.cycle = self.detector.record(self.generation, self.hash(board));
+        self.extinction = self
+            .extinction_detector
+            .record(self.generation, board.population());
+    }
+
+    fn stop(&self, config: &Config) -> Option<Stop> {
+        // The reason to stop now, if the options ask for one
+        match (self.extinction, self.cycle) {
+            (Some(extinction), _) if config.stop_on_extinction => {
+                Some(Stop::Extinction(extinction))
             }
+            (_, Some(cycle)) if config.stop_on_cycle => Some(Stop::Cycle(cycle)),
+            _ => None,
+        }
+    }
+
+    fn entropy(&self, board: &dyn Life) -> f64 {
+        // Entropy of the cells in view, taking every alive cell to be
+        // in it, which it is unless the board has grown
+        entropy(self.population(board), (self.width * self.height) as usize)
+    }
+
+    fn stats(&self, board: &dyn Life, rule: Rule) -> String {
+        // The generation, population, entropy and rule, with the cells
+        // born and died in the last generation on boards that count them
+        let changes = match self.last_tick {
+            Some(stats) => format!("  Born: {}  Died: {}", stats.births, stats.deaths),
+            None => String::new(),
+        };
+        format!(
+            "Generation: {}  Population: {}{}  Entropy: {:.2}  Rule: {}",
+            self.generation,
+            self.population(board),
+            changes,
+            self.entropy(board),
+            rule
+        )
+    }
+
+    fn status(&self) -> String {
+        // An empty board is also a cycle, but dying out is the news
+        match (self.extinction, self.cycle) {
+            (Some(extinction), _) => format!("  The {}", extinction),
+            (None, Some(cycle)) => format!("  The board {}", cycle),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+// How long the last generation took to compute and the last frame to
+// draw, to tell a board too big to keep up from a slow terminal
+#[derive(Debug, Default, Clone, Copy)]
+struct FrameTimes {
+    tick: Duration,
+    render: Duration,
+}
+
+impl FrameTimes {
+    fn status(&self, delay: Duration) -> String {
+        // Shown with the stats, warning when a generation takes longer to
+        // compute than the delay between generations
+        let ms = |time: Duration| time.as_secs_f64() * 1000.0;
+        let behind = if self.tick > delay {
+            "  simulation falling behind"
+        } else {
+            ""
+        };
+        format!(
+            "Tick: {:.1}ms Render: {:.1}ms{}",
+            ms(self.tick),
+            ms(self.render),
+            behind
+        )
+    }
+}
+
+#[cfg(not(feature = "interactive"))]
+fn run(
+    board: &mut dyn Life,
+    width: i64,
+    height: i64,
+    config: &Config,
+    stats_out: &mut Option<StatsWriter>,
+) -> Option<Stop> {
+    // Plays the simulation until the options say to stop, if they do,
+    // with a sparkline of the population as wide as the board
+    use gameoflife_rs::{
+        life::render,
+        render::sparkline::{sparkline, PopulationHistory},
+    };
+
+    let mut watch = Watch::new(board, width, height);
+    let mut populations = PopulationHistory::new(width as usize);
+    let mut times = FrameTimes::default();
+    populations.record(watch.population(board), watch.entropy(board));
+    println!("{}", render(board, (1, 1), width, height));
+    if config.status {
+        println!("{}", watch.stats(board, config.rule));
+    }
+    loop {
+        if let Some(stop) = watch.stop(config) {
+            return Some(stop);
+        }
+        std::thread::sleep(FRAME_DELAY);
+        let start = Instant::now();
+        watch.step(board, stats_out);
+        times.tick = start.elapsed();
+        populations.record(watch.population(board), watch.entropy(board));
+
+        // The frame shows how long the one before it took to draw
+        let start = Instant::now();
+        print!("\x1B[2J\x1B[1;1H");
+        print!("{}", render(board, (1, 1), width, height));
+        println!(
+            "{}",
+            sparkline(&populations.recent(width as usize), !config.ascii)
+        );
+        if config.status {
+            println!(
+                "{}  {}",
+                watch.stats(board, config.rule),
+                times.status(FRAME_DELAY)
+            );
         }
+        println!("{}", watch.status().trim_start());
+        times.render = start.elapsed();
     }
 }
 
-impl fmt::Display for Board {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for y in 1..BOARD_HEIGHT {
-            for x in 1..BOARD_WIDTH {
-                if let Some(cell) = self.get_cell((x, y)) {
-                    write!(f, "{}", cell)?;
+#[cfg(feature = "interactive")]
+fn run_interactive(
+    board: &mut dyn Life,
+    width: i64,
+    height: i64,
+    config: &Config,
+    stats_out: &mut Option<StatsWriter>,
+) -> io::Result<Option<Stop>> {
+    // Runs the simulation in raw mode so keypresses can be read as
+    // they happen. The guard restores the terminal however the loop
+    // ends, even on a panic. Returns why it stopped, unless it was quit.
+
+    let _guard = TerminalGuard::new()?;
+    let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let mut renderer = TuiRenderer::new(terminal, (1, 1), width, height);
+    renderer.set_ascii(config.ascii);
+    Simulation::new(board, renderer, width, height, config, stats_out).run()
+}
+
+// The interactive view: the board, drawn by the renderer, and the
+// controls that drive it. Reading events from the terminal is left to
+// run, so everything else can be driven by hand in tests.
+#[cfg(feature = "interactive")]
+struct Simulation<'a, B: Backend> {
+    board: &'a mut dyn Life,
+    renderer: TuiRenderer<B>,
+    controls: Controls,
+    watch: Watch,
+    config: &'a Config,
+    stats_out: &'a mut Option<StatsWriter>,
+    width: i64,
+    height: i64,
+    // When the next generation is due while running
+    next_tick: Instant,
+    times: FrameTimes,
+}
+
+#[cfg(feature = "interactive")]
+impl<'a, B: Backend> Simulation<'a, B> {
+    fn new(
+        board: &'a mut dyn Life,
+        mut renderer: TuiRenderer<B>,
+        width: i64,
+        height: i64,
+        config: &'a Config,
+        stats_out: &'a mut Option<StatsWriter>,
+    ) -> Self {
+        let controls = Controls::new((1, 1), width, height);
+        let watch = Watch::new(board, width, height);
+        renderer.record_population(watch.population(board), watch.entropy(board));
+        Simulation {
+            next_tick: Instant::now() + controls.delay,
+            board,
+            renderer,
+            controls,
+            watch,
+            config,
+            stats_out,
+            width,
+            height,
+            times: FrameTimes::default(),
+        }
+    }
+
+    fn run(&mut self) -> io::Result<Option<Stop>> {
+        // Draws a frame, then waits for an event or for the next
+        // generation to be due, whichever comes first, until the
+        // options say to stop or someone quits
+        loop {
+            self.render()?;
+            if let Some(stop) = self.watch.stop(self.config) {
+                return Ok(Some(stop));
+            }
+            let timeout = self.next_tick.saturating_duration_since(Instant::now());
+            if event::poll(timeout)? {
+                self.handle_event(event::read()?)?;
+                if self.controls.quit {
+                    return Ok(None);
                 }
+            } else {
+                self.tick();
             }
-            writeln!(f)?;
         }
-        Ok(())
     }
-}
 
-fn main() {
-    // rand boilerplate
-    let dist = Bernoulli::new(0.5).unwrap();
-    let mut rng = rand::thread_rng();
+    fn render(&mut self) -> io::Result<()> {
+        let cursor = if self.controls.editing {
+            Some(self.controls.cursor)
+        } else {
+            None
+        };
+        let com = match center_of_mass(self.board, (1, 1), self.width, self.height) {
+            Some((x, y)) => format!("CoM: ({:.1}, {:.1})", x, y),
+            None => String::from("CoM: none"),
+        };
+        let mut status = format!("{}  {}{}", self.controls.status(), com, self.watch.status());
+        if self.config.status {
+            // The frame shows how long the one before it took to draw
+            status = format!(
+                "{}  {}\n{}",
+                self.watch.stats(self.board, self.config.rule),
+                self.times.status(self.controls.delay),
+                status
+            );
+        }
+        let start = Instant::now();
+        let drawn = self.renderer.draw(
+            self.board,
+            cursor,
+            self.controls.zoom_level,
+            &status,
+            self.controls.hints(),
+        );
+        self.times.render = start.elapsed();
+        drawn
+    }
+
+    fn handle_event(&mut self, event: Event) -> io::Result<()> {
+        // Applies a keypress or click. A resized terminal is repainted
+        // from scratch, with the board fitted to its new size.
+        let before = self.watch.hash(self.board);
+        match event {
+            Event::Key(key) => self.controls.handle_key(key, self.board),
+            Event::Mouse(mouse) => {
+                let cell = self.renderer.cell_at(mouse.column, mouse.row);
+                self.controls.handle_mouse(mouse, cell, self.board);
+            }
+            Event::Resize(..) => self.renderer.clear()?,
+            _ => (),
+        }
+        if self.watch.hash(self.board) != before {
+            self.watch.restart(self.board);
+        }
+        if self.controls.take_step() {
+            self.step();
+        }
+        Ok(())
+    }
 
-    //Initialize the board with randomly alive/dead cells
-    let mut board = Board::new();
-    for x in 1..BOARD_WIDTH {
-        for y in 1..BOARD_HEIGHT {
-            let mut cell = board.get_cell_mut((x, y)).unwrap();
-            cell.alive = dist.sample(&mut rng);
+    fn tick(&mut self) {
+        // Takes the generation that has come due, unless paused
+        if !self.controls.paused {
+            let was_alive = self.watch.extinction.is_none();
+            self.step();
+            // Nothing more will happen, so stop ticking until someone
+            // edits the board or unpauses
+            if was_alive && self.watch.extinction.is_some() {
+                self.controls.paused = true;
+            }
         }
+        self.next_tick = Instant::now() + self.controls.delay;
     }
 
-    println!("{}", board);
-    loop {
-        board.tick();
-        print!("\x1B[2J\x1B[1;1H");
-        print!("{}", board);
-        sleep(time::Duration::from_millis(100));
+    fn step(&mut self) {
+        let start = Instant::now();
+        self.watch.step(self.board, self.stats_out);
+        self.times.tick = start.elapsed();
+        self.renderer.record_population(
+            self.watch.population(self.board),
+            self.watch.entropy(self.board),
+        );
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::Rng;
-    use std::vec;
 
-    fn setup() -> (usize, usize, Board, rand::prelude::ThreadRng) {
-        let mut rng = rand::thread_rng();
-        let board = Board::new();
-        let x_coord = rng.gen_range(1..BOARD_WIDTH);
-        let y_coord = rng.gen_range(1..BOARD_HEIGHT);
-        (x_coord, y_coord, board, rng)
+    fn parse(args: &[&str]) -> Result<Config, String> {
+        Config::from_args(args.iter().map(|arg| arg.to_string()))
     }
 
-    #[test]
-    fn cell_flip() {
-        let (x_coord, y_coord, mut board, _rng) = setup();
-        let cell = board.get_cell_mut((x_coord, y_coord)).unwrap();
-        cell.flip();
+    #[cfg(feature = "interactive")]
+    fn key(code: event::KeyCode) -> Event {
+        Event::Key(event::KeyEvent::new(code, event::KeyModifiers::NONE))
+    }
 
-        assert_eq!(board.get_cell((x_coord, y_coord)).unwrap().alive, true)
+    #[cfg(feature = "interactive")]
+    fn simulate<'a>(
+        board: &'a mut Board,
+        config: &'a Config,
+        stats_out: &'a mut Option<StatsWriter>,
+    ) -> Simulation<'a, ratatui::backend::TestBackend> {
+        // A simulation of a 10x10 view, drawn to a test terminal with
+        // just enough room for it
+        let terminal = Terminal::new(ratatui::backend::TestBackend::new(12, 20)).unwrap();
+        let renderer = TuiRenderer::new(terminal, (1, 1), 10, 10);
+        Simulation::new(board, renderer, 10, 10, config, stats_out)
     }
 
+    #[cfg(feature = "interactive")]
     #[test]
-    fn board_get_cell() {
-        let (x_coord, y_coord, mut board, _rng) = setup();
-        board.board[x_coord][y_coord].alive = true;
-        assert_eq!(board.get_cell((x_coord, y_coord)).unwrap().alive, true);
+    fn simulation_keys() {
+        let mut board = Board::from_rows(&[".....", "..#..", "..#..", "..#..", "....."]).unwrap();
+        let config = parse(&[]).unwrap();
+        let mut stats_out = None;
+        let mut simulation = simulate(&mut board, &config, &mut stats_out);
+        simulation.render().unwrap();
+
+        simulation.tick();
+        assert_eq!(simulation.watch.generation, 1);
+        assert!(simulation.board.is_alive_at((2, 3)));
+
+        // Paused, generations only come one at a time with '.'
+        simulation
+            .handle_event(key(event::KeyCode::Char(' ')))
+            .unwrap();
+        simulation.tick();
+        assert_eq!(simulation.watch.generation, 1);
+        simulation
+            .handle_event(key(event::KeyCode::Char('.')))
+            .unwrap();
+        assert_eq!(simulation.watch.generation, 2);
+        assert!(simulation.board.is_alive_at((3, 2)));
+
+        simulation
+            .handle_event(key(event::KeyCode::Char('q')))
+            .unwrap();
+        assert!(simulation.controls.quit);
     }
 
+    #[cfg(feature = "interactive")]
     #[test]
-    fn boarder_1() {
-        let (_x_coord, _y_coord, board, _rng) = setup();
-        assert_eq!(board.get_cell((0, 0)), None);
+    fn simulation_click_and_extinction() {
+        let mut board = Board::with_size(10, 10);
+        let config = parse(&[]).unwrap();
+        let mut stats_out = None;
+        let mut simulation = simulate(&mut board, &config, &mut stats_out);
+        simulation.render().unwrap();
+
+        // The view fills the terminal's width inside its border, and is
+        // a row down from the top, centred in the height above the status
+        let click = Event::Mouse(event::MouseEvent {
+            kind: event::MouseEventKind::Down(event::MouseButton::Left),
+            column: 3,
+            row: 3,
+            modifiers: event::KeyModifiers::NONE,
+        });
+        simulation.handle_event(click).unwrap();
+        assert!(simulation.board.is_alive_at((3, 2)));
+        assert!(simulation.watch.extinction.is_none());
+
+        // The lone cell dies, and the view pauses on its own
+        simulation.tick();
+        assert!(simulation.watch.extinction.is_some());
+        assert!(simulation.controls.paused);
+        simulation.handle_event(Event::Resize(40, 30)).unwrap();
+        simulation.render().unwrap();
     }
 
     #[test]
-    fn boarder_2() {
-        let (_x_coord, _y_coord, board, _rng) = setup();
-        assert_eq!(board.get_cell((BOARD_WIDTH, BOARD_HEIGHT)), None);
+    fn frame_times_status() {
+        let mut times = FrameTimes {
+            tick: Duration::from_micros(2500),
+            render: Duration::from_millis(14),
+        };
+        let delay = Duration::from_millis(100);
+        assert_eq!(times.status(delay), "Tick: 2.5ms Render: 14.0ms");
+        times.tick = Duration::from_millis(120);
+        assert_eq!(
+            times.status(delay),
+            "Tick: 120.0ms Render: 14.0ms  simulation falling behind"
+        );
     }
 
     #[test]
-    fn board_count_adjacent_alive_1() {
-        let (_x_coord, _y_coord, board, _rng) = setup();
-        board.count_adjacent_alive((1, 1)); //Should not panic
+    fn config_engine() {
+        assert_eq!(parse(&[]).unwrap().engine, Engine::Dense);
+        assert_eq!(
+            parse(&["--engine", "sparse"]).unwrap().engine,
+            Engine::Sparse
+        );
+        assert_eq!(
+            parse(&["--engine", "bitpacked"]).unwrap().engine,
+            Engine::BitPacked
+        );
+        assert_eq!(
+            parse(&["--engine", "hashlife"]).unwrap().engine,
+            Engine::HashLife
+        );
+        assert!(parse(&["--engine", "quantum"]).is_err());
+        assert!(parse(&["--engine"]).is_err());
     }
 
     #[test]
-    fn board_count_adjacent_alive_2() {
-        let (x_coord, y_coord, mut board, mut rng) = setup();
-
-        // Randomly select three adjacent cells to make alive
-        let targets = [
-            rng.gen_range(0..9),
-            rng.gen_range(0..9),
-            rng.gen_range(0..9),
-        ];
-
-        let mut counter = 0;
-        for target in targets.iter() {
-            match target {
-                1 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord - 1, y_coord - 1)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                2 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord - 1, y_coord)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                3 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord - 1, y_coord + 1)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                4 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord, y_coord - 1)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                5 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord, y_coord + 1)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                6 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord + 1, y_coord - 1)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                7 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord + 1, y_coord)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                8 => {
-                    if let Some(cell) = board.get_cell_mut((x_coord + 1, y_coord + 1)) {
-                        if !cell.alive {
-                            cell.alive = true;
-                            counter += 1;
-                        }
-                    }
-                }
-                _ => (),
-            }
-        }
-
-        assert_eq!(board.count_adjacent_alive((x_coord, y_coord)), counter)
+    fn config_grow() {
+        assert_eq!(parse(&[]).unwrap().grow, None);
+        assert_eq!(
+            parse(&["--grow", "500x300"]).unwrap().grow,
+            Some((500, 300))
+        );
+        assert!(parse(&["--grow", "500"]).is_err());
+        assert!(parse(&["--grow", "500x300x2"]).is_err());
     }
 
     #[test]
-    fn board_get_cells_to_flip_1() {
-        let (_x_coord, _y_coord, mut board, mut rng) = setup();
+    fn config_threads() {
+        assert_eq!(parse(&[]).unwrap().threads, None);
+        assert_eq!(parse(&["--threads", "4"]).unwrap().threads, Some(4));
+        assert!(parse(&["--threads", "0"]).is_err());
+        assert!(parse(&["--threads", "all"]).is_err());
+    }
 
-        let coords1: (usize, usize) = (
-            rng.gen_range(0..BOARD_WIDTH),
-            rng.gen_range(1..BOARD_HEIGHT),
-        );
-        let coords2: (usize, usize) = (
-            rng.gen_range(0..BOARD_WIDTH),
-            rng.gen_range(1..BOARD_HEIGHT),
+    #[test]
+    fn config_stop_on_extinction() {
+        assert_eq!(
+            parse(&[]).unwrap().stop_on_extinction,
+            !cfg!(feature = "interactive")
         );
-        let coords3: (usize, usize) = (
-            rng.gen_range(0..BOARD_WIDTH),
-            rng.gen_range(1..BOARD_HEIGHT),
+        assert!(parse(&["--stop-on-extinction"]).unwrap().stop_on_extinction);
+        assert!(
+            !parse(&["--stop-on-extinction", "--no-stop-on-extinction"])
+                .unwrap()
+                .stop_on_extinction
         );
+    }
 
-        for coords in [coords1, coords2, coords3].iter() {
-            board.get_cell_mut(*coords).unwrap().flip();
-        }
+    #[test]
+    fn config_rule() {
+        assert_eq!(parse(&[]).unwrap().rule, Rule::CONWAY);
+        assert_eq!(
+            parse(&["--rule", "B36/S23"]).unwrap().rule,
+            "B36/S23".parse().unwrap()
+        );
+        assert!(parse(&["--rule", "B9/S23"]).is_err());
+        assert!(parse(&["--rule"]).is_err());
+    }
 
-        let mut flipped = vec![coords1, coords2, coords3];
-        let mut test_result = board.get_cells_to_flip();
-        flipped.sort();
-        test_result.sort();
-        assert_eq!(flipped, test_result);
+    #[test]
+    fn config_status() {
+        assert!(parse(&[]).unwrap().status);
+        assert!(!parse(&["--no-status"]).unwrap().status);
     }
 
     #[test]
-    fn board_get_cells_to_flip_2() {
-        let (_x_coord, _y_coord, mut board, mut _rng) = setup();
+    fn config_stats_out() {
+        assert_eq!(parse(&[]).unwrap().stats_out, None);
+        assert_eq!(
+            parse(&["--stats-out", "run.csv"]).unwrap().stats_out,
+            Some(PathBuf::from("run.csv"))
+        );
+        assert!(parse(&["--stats-out"]).is_err());
+    }
 
-        let coords1: (usize, usize) = (4, 4);
-        let coords2: (usize, usize) = (4, 5);
-        let coords3: (usize, usize) = (5, 5);
+    #[test]
+    fn config_metrics() {
+        let config = parse(&[]).unwrap();
+        assert_eq!(
+            (config.metrics, config.generations),
+            (None, METRICS_GENERATIONS)
+        );
+        let config = parse(&["--metrics", "run.csv", "--generations", "50"]).unwrap();
+        assert_eq!(config.metrics, Some(PathBuf::from("run.csv")));
+        assert_eq!(config.generations, 50);
+        assert!(parse(&["--metrics"]).is_err());
+        assert!(parse(&["--generations", "-1"]).is_err());
+    }
 
-        for coords in [coords1, coords2, coords3].iter() {
-            board.get_cell_mut(*coords).unwrap().flip();
-        }
+    #[test]
+    fn config_import_image() {
+        assert_eq!(parse(&[]).unwrap().image, None);
+        let config = parse(&["--import-image", "glider.png"]).unwrap();
+        assert_eq!(config.image, Some(PathBuf::from("glider.png")));
+        assert!(parse(&["--import-image"]).is_err());
+        assert!(parse(&["--import-image", "glider.png", "--pattern", "name:glider"]).is_err());
 
-        let to_flip: Vec<(usize, usize)> = vec![(5, 4)];
-        let test_result = board.get_cells_to_flip();
-        assert_eq!(to_flip, test_result);
+        // The imported board isn't seeded over
+        let board = dense_board(&config, Some(Board::with_size(4, 4)), 9, 9);
+        assert_eq!((board.width(), board.height()), (4, 4));
+        assert_eq!(board.population(), 0);
     }
 
     #[test]
-    fn board_get_cells_to_flip_3() {
-        let (_x_coord, _y_coord, mut board, mut _rng) = setup();
-
-        let coords1: (usize, usize) = (1, 1);
+    fn metrics_reconcile() {
+        // Each generation's births and deaths account for the change in
+        // population, and match the cells that were due to flip
+        let path = env::temp_dir().join("gameoflife-rs-metrics_reconcile.csv");
+        let mut board = Board::with_size(40, 30);
+        board.reset_random(9, 0.4);
+        let start = board.population() as i64;
+        let mut expected = Vec::new();
+        let mut ticked = board.clone();
+        for _ in 0..60 {
+            let flips = ticked.get_cells_to_flip();
+            let born = flips
+                .iter()
+                .filter(|&&coords| !ticked.get_cell(coords).unwrap().alive)
+                .count() as i64;
+            expected.push((born, flips.len() as i64 - born));
+            ticked.tick();
+        }
 
-        for coords in [coords1].iter() {
-            board.get_cell_mut(*coords).unwrap().flip();
+        write_metrics(&mut board, 60, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let rows: Vec<Vec<i64>> = csv
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(rows.len(), 60);
+        let mut population = start;
+        for (row, &(born, died)) in rows.iter().zip(&expected) {
+            assert_eq!((row[2], row[3]), (born, died));
+            assert_eq!(row[1] - population, born - died);
+            population = row[1];
         }
+        assert_eq!(population, board.population() as i64);
+        assert_eq!(board, ticked);
+    }
 
-        let to_flip: Vec<(usize, usize)> = vec![(1, 1)];
-        let test_result = board.get_cells_to_flip();
-        assert_eq!(to_flip, test_result);
+    #[test]
+    fn config_ascii() {
+        assert!(!parse(&[]).unwrap().ascii);
+        assert!(parse(&["--ascii"]).unwrap().ascii);
     }
 
     #[test]
-    fn board_get_cells_to_flip_4() {
-        let (_x_coord, _y_coord, mut board, mut _rng) = setup();
+    fn config_pattern() {
+        assert_eq!(parse(&[]).unwrap().pattern, None);
+        let pattern = parse(&["--pattern", "name:Pulsar"])
+            .unwrap()
+            .pattern
+            .unwrap();
+        assert_eq!(pattern.name, "pulsar");
 
-        let coords1: (usize, usize) = (1, 1);
-        let coords2: (usize, usize) = (1, 2);
-        let coords3: (usize, usize) = (2, 2);
-        let coords4: (usize, usize) = (2, 1);
+        assert!(parse(&["--pattern", "glider"]).is_err());
+        assert!(parse(&["--pattern"]).is_err());
+        assert_eq!(
+            parse(&["--pattern", "name:glid"]),
+            Err(String::from(
+                "--pattern: no pattern called glid. Did you mean glider, gosper_glider_gun?"
+            ))
+        );
+        assert_eq!(
+            parse(&["--pattern", "name:zzz"]),
+            Err(String::from("--pattern: no pattern called zzz"))
+        );
 
-        for coords in [coords1, coords2, coords3, coords4].iter() {
-            board.get_cell_mut(*coords).unwrap().flip();
-        }
+        let mut board = Board::new();
+        let config = parse(&["--pattern", "name:glider"]).unwrap();
+        seed(&mut board, &config, 9, 9).unwrap();
+        let cells: Vec<_> = board.live_cells().collect();
+        assert_eq!(cells, vec![(5, 4), (6, 5), (4, 6), (5, 6), (6, 6)]);
 
-        let to_flip: Vec<(usize, usize)> = vec![];
-        let test_result = board.get_cells_to_flip();
-        assert_eq!(to_flip, test_result);
+        // The dense board's builder places it in the same spot
+        let board = dense_board(&config, None, 9, 9);
+        assert_eq!((board.width(), board.height()), (9, 9));
+        assert_eq!(board.live_cells().collect::<Vec<_>>(), cells);
+
+        // Too big for the view, so nothing is placed
+        let mut board = Board::new();
+        let config = parse(&["--pattern", "name:gosper_glider_gun"]).unwrap();
+        assert!(matches!(
+            seed(&mut board, &config, 20, 20),
+            Err(BoardError::PatternTooLarge(PatternTooLargeError {
+                pattern: (36, 9),
+                board: (20, 20)
+            }))
+        ));
+        assert_eq!(board.population(), 0);
     }
 
     #[test]
-    fn board_get_cells_to_flip_5() {
-        let (_x_coord, _y_coord, mut board, mut _rng) = setup();
-
-        let coords1: (usize, usize) = (1, 1);
-        let coords2: (usize, usize) = (1, 2);
-        let coords3: (usize, usize) = (2, 2);
-        let coords4: (usize, usize) = (2, 1);
-        let coords5: (usize, usize) = (3, 1);
-        let coords6: (usize, usize) = (3, 2);
-
-        for coords in [coords1, coords2, coords3, coords4, coords5, coords6].iter() {
-            board.get_cell_mut(*coords).unwrap().flip();
-        }
-
-        let mut to_flip: Vec<(usize, usize)> = vec![(2, 1), (2, 2), (2, 3)];
-        let mut test_result = board.get_cells_to_flip();
-        to_flip.sort();
-        test_result.sort();
-        assert_eq!(to_flip, test_result);
+    fn config_stop_on_cycle() {
+        assert!(!parse(&[]).unwrap().stop_on_cycle);
+        assert!(
+            parse(&["--stop-on-cycle", "--engine", "sparse"])
+                .unwrap()
+                .stop_on_cycle
+        );
     }
 }
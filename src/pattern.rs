@@ -0,0 +1,111 @@
+// Rotations and reflections of patterns given as the offsets of their
+// alive cells, for placing a pattern in any orientation without working
+// out the coordinates by hand. Each transform keeps the top-left corner
+// of the pattern's bounding box where it was, and returns the offsets
+// sorted.
+
+pub mod registry;
+
+fn bounds(offsets: &[(usize, usize)]) -> ((usize, usize), (usize, usize)) {
+    // Top-left and bottom-right corners of the bounding box
+    let min_x = offsets.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = offsets.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let max_x = offsets.iter().map(|&(x, _)| x).max().unwrap_or(0);
+    let max_y = offsets.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    ((min_x, min_y), (max_x, max_y))
+}
+
+fn transform<F>(offsets: &[(usize, usize)], f: F) -> Vec<(usize, usize)>
+where
+    F: Fn((usize, usize), (usize, usize), (usize, usize)) -> (usize, usize),
+{
+    let (min, max) = bounds(offsets);
+    let mut transformed: Vec<_> = offsets.iter().map(|&offset| f(offset, min, max)).collect();
+    transformed.sort_unstable();
+    transformed
+}
+
+pub fn rotate_90(offsets: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // Turns the pattern a quarter turn clockwise, with y going down
+    // the screen
+    transform(offsets, |(x, y), min, max| {
+        (min.0 + (max.1 - y), min.1 + (x - min.0))
+    })
+}
+
+pub fn flip_horizontal(offsets: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // Mirrors the pattern left to right
+    transform(offsets, |(x, y), min, max| (min.0 + max.0 - x, y))
+}
+
+pub fn flip_vertical(offsets: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // Mirrors the pattern top to bottom
+    transform(offsets, |(x, y), min, max| (x, min.1 + max.1 - y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{board::Board, life::Life};
+
+    // Heading down and to the right
+    const GLIDER: [(usize, usize); 5] = [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)];
+
+    fn run(
+        offsets: &[(usize, usize)],
+        generations: usize,
+    ) -> ((usize, usize), Vec<(usize, usize)>) {
+        // Runs the pattern in the middle of an empty board, returning
+        // where its bounding box ends up and the cells' offsets in it
+        let mut board = Board::with_size(20, 20);
+        for &(x, y) in offsets {
+            board.set_alive_at((x as i64 + 8, y as i64 + 8), true);
+        }
+        board.tick_n(generations as u64);
+        let cells = board.find_islands().concat();
+        let (min, _) = bounds(&cells);
+        let mut shape: Vec<_> = cells.iter().map(|&(x, y)| (x - min.0, y - min.1)).collect();
+        shape.sort_unstable();
+        (min, shape)
+    }
+
+    #[test]
+    fn pattern_rotate() {
+        let mut glider = GLIDER.to_vec();
+        for _ in 0..4 {
+            glider = rotate_90(&glider);
+            // Every orientation is still a glider, back in shape four
+            // generations on and a cell away diagonally
+            let (start, shape) = run(&glider, 0);
+            let (end, moved_shape) = run(&glider, 4);
+            assert_eq!(shape, moved_shape);
+            assert_eq!((start.0.abs_diff(end.0), start.1.abs_diff(end.1)), (1, 1));
+        }
+        assert_eq!(glider, GLIDER.to_vec());
+
+        assert_eq!(
+            rotate_90(&GLIDER),
+            vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn pattern_flip() {
+        assert_eq!(
+            flip_horizontal(&GLIDER),
+            vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 2)]
+        );
+        assert_eq!(
+            flip_vertical(&GLIDER),
+            vec![(0, 0), (1, 0), (1, 2), (2, 0), (2, 1)]
+        );
+        assert_eq!(flip_horizontal(&flip_horizontal(&GLIDER)), GLIDER.to_vec());
+        assert_eq!(flip_vertical(&flip_vertical(&GLIDER)), GLIDER.to_vec());
+
+        // The pattern stays in its place
+        let blinker = [(5, 7), (6, 7), (7, 7)];
+        assert_eq!(flip_vertical(&blinker), blinker.to_vec());
+        assert_eq!(rotate_90(&blinker), vec![(5, 7), (5, 8), (5, 9)]);
+        assert!(rotate_90(&[]).is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+// Well-known patterns by name, for starting a board from one of them
+// without typing in its cells, and for finding them by what they do.
+
+use std::fmt;
+
+use self::Category::*;
+
+// What a pattern does when left alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    // Never changes
+    StillLife,
+    // Returns to its starting shape in the same place
+    Oscillator,
+    // Returns to its starting shape somewhere else
+    Spaceship,
+    // Oscillates while sending out a stream of spaceships
+    Gun,
+    // Small, but takes a long time to settle down
+    Methuselah,
+}
+
+impl Category {
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::StillLife => "still_life",
+            Category::Oscillator => "oscillator",
+            Category::Spaceship => "spaceship",
+            Category::Gun => "gun",
+            Category::Methuselah => "methuselah",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternInfo {
+    // Lower case, with words joined by underscores
+    pub name: &'static str,
+    pub category: Category,
+    // Number of generations before the pattern repeats, or None for a
+    // methuselah, which never does. Still lifes have a period of 1.
+    pub period: Option<u32>,
+    // How fast a spaceship moves, as a fraction of c, one cell per
+    // generation, such as "c/4"
+    pub speed: Option<&'static str>,
+    // Offsets of the alive cells from the top-left of the pattern's
+    // bounding box, sorted
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl PatternInfo {
+    pub fn new(
+        name: &'static str,
+        category: Category,
+        period: Option<u32>,
+        speed: Option<&'static str>,
+        art: &str,
+    ) -> PatternInfo {
+        // Takes the cells from `art`, rows of '#' for alive cells and
+        // anything else for dead ones
+        let mut cells: Vec<_> = art
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+        cells.sort_unstable();
+        PatternInfo {
+            name,
+            category,
+            period,
+            speed,
+            cells,
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        // Width and height of the bounding box
+        let width = self.cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = self.cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        (width, height)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternRegistry {
+    patterns: Vec<PatternInfo>,
+}
+
+impl PatternRegistry {
+    pub fn new() -> PatternRegistry {
+        // An empty registry. PatternRegistry::default comes with the
+        // well-known patterns.
+        PatternRegistry {
+            patterns: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, pattern: PatternInfo) {
+        // Adds a pattern, replacing any with the same name
+        self.patterns.retain(|p| p.name != pattern.name);
+        self.patterns.push(pattern);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PatternInfo> {
+        // The pattern called `name`, ignoring case
+        self.patterns
+            .iter()
+            .find(|pattern| pattern.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn search(&self, query: &str) -> Vec<&PatternInfo> {
+        // Patterns whose name or category contains `query`, ignoring
+        // case, in the order they were added
+        let query = query.to_lowercase();
+        self.patterns
+            .iter()
+            .filter(|pattern| {
+                pattern.name.contains(&query) || pattern.category.name().contains(&query)
+            })
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PatternInfo> {
+        self.patterns.iter()
+    }
+}
+
+impl Default for PatternRegistry {
+    fn default() -> PatternRegistry {
+        let mut registry = PatternRegistry::new();
+        for &(name, category, period, speed, art) in WELL_KNOWN.iter() {
+            registry.add(PatternInfo::new(name, category, period, speed, art));
+        }
+        registry
+    }
+}
+
+// Name, category, period, speed and cells of a pattern, as taken by
+// PatternInfo::new
+type Entry = (
+    &'static str,
+    Category,
+    Option<u32>,
+    Option<&'static str>,
+    &'static str,
+);
+
+// The patterns PatternRegistry::default starts with
+#[rustfmt::skip]
+const WELL_KNOWN: [Entry; 31] = [
+    ("block", StillLife, Some(1), None, "##\n##"),
+    ("beehive", StillLife, Some(1), None, ".##.\n#..#\n.##."),
+    ("loaf", StillLife, Some(1), None, ".##.\n#..#\n.#.#\n..#."),
+    ("boat", StillLife, Some(1), None, "##.\n#.#\n.#."),
+    ("tub", StillLife, Some(1), None, ".#.\n#.#\n.#."),
+    ("ship", StillLife, Some(1), None, "##.\n#.#\n.##"),
+    ("pond", StillLife, Some(1), None, ".##.\n#..#\n#..#\n.##."),
+    ("snake", StillLife, Some(1), None, "##.#\n#.##"),
+    ("eater_1", StillLife, Some(1), None, "##..\n#.#.\n..#.\n..##"),
+    ("blinker", Oscillator, Some(2), None, "###"),
+    ("toad", Oscillator, Some(2), None, ".###\n###."),
+    ("beacon", Oscillator, Some(2), None, "##..\n##..\n..##\n..##"),
+    ("clock", Oscillator, Some(2), None, "..#.\n#.#.\n.#.#\n.#.."),
+    ("pulsar", Oscillator, Some(3), None,
+        "..###...###..\n\
+         .............\n\
+         #....#.#....#\n\
+         #....#.#....#\n\
+         #....#.#....#\n\
+         ..###...###..\n\
+         .............\n\
+         ..###...###..\n\
+         #....#.#....#\n\
+         #....#.#....#\n\
+         #....#.#....#\n\
+         .............\n\
+         ..###...###.."),
+    ("octagon_2", Oscillator, Some(5), None,
+        "...##...\n\
+         ..#..#..\n\
+         .#....#.\n\
+         #......#\n\
+         #......#\n\
+         .#....#.\n\
+         ..#..#..\n\
+         ...##..."),
+    ("figure_eight", Oscillator, Some(8), None,
+        "###...\n###...\n###...\n...###\n...###\n...###"),
+    ("koks_galaxy", Oscillator, Some(8), None,
+        "######.##\n\
+         ######.##\n\
+         .......##\n\
+         ##.....##\n\
+         ##.....##\n\
+         ##.....##\n\
+         ##.......\n\
+         ##.######\n\
+         ##.######"),
+    ("tumbler", Oscillator, Some(14), None,
+        ".##.##.\n.##.##.\n..#.#..\n#.#.#.#\n#.#.#.#\n##...##"),
+    ("pentadecathlon", Oscillator, Some(15), None,
+        "..#....#..\n##.####.##\n..#....#.."),
+    ("glider", Spaceship, Some(4), Some("c/4"), ".#.\n..#\n###"),
+    ("lightweight_spaceship", Spaceship, Some(4), Some("c/2"),
+        ".#..#\n#....\n#...#\n####."),
+    ("middleweight_spaceship", Spaceship, Some(4), Some("c/2"),
+        "...#..\n.#...#\n#.....\n#....#\n#####."),
+    ("heavyweight_spaceship", Spaceship, Some(4), Some("c/2"),
+        "...##..\n.#....#\n#......\n#.....#\n######."),
+    ("gosper_glider_gun", Gun, Some(30), None,
+        "........................#...........\n\
+         ......................#.#...........\n\
+         ............##......##............##\n\
+         ...........#...#....##............##\n\
+         ##........#.....#...##..............\n\
+         ##........#...#.##....#.#...........\n\
+         ..........#.....#.......#...........\n\
+         ...........#...#....................\n\
+         ............##......................"),
+    ("r_pentomino", Methuselah, None, None, ".##\n##.\n.#."),
+    ("diehard", Methuselah, None, None, "......#.\n##......\n.#...###"),
+    ("acorn", Methuselah, None, None, ".#.....\n...#...\n##..###"),
+    ("pi_heptomino", Methuselah, None, None, "###\n#.#\n#.#"),
+    ("b_heptomino", Methuselah, None, None, "#.##\n###.\n.#.."),
+    ("thunderbird", Methuselah, None, None, "###\n...\n.#.\n.#.\n.#."),
+    ("herschel", Methuselah, None, None, "#..\n###\n#.#\n..#"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+
+    fn run(pattern: &PatternInfo, generations: usize) -> ((usize, usize), Vec<(usize, usize)>) {
+        // Runs the pattern in the middle of a board large enough that it
+        // never reaches the edges, returning where its bounding box ends
+        // up and the cells' offsets in it
+        let mut board = Board::with_size(100, 100);
+        for &(x, y) in &pattern.cells {
+            board.set_alive((x + 40, y + 40), true).unwrap();
+        }
+        board.tick_n(generations as u64);
+        let cells: Vec<_> = board.live_cells().collect();
+        let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let mut shape: Vec<_> = cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+        shape.sort_unstable();
+        ((min_x, min_y), shape)
+    }
+
+    #[test]
+    fn pattern_registry_periods() {
+        // Every repeating pattern is back in shape after exactly its
+        // period, in the same place unless it's a spaceship
+        let registry = PatternRegistry::default();
+        assert_eq!(registry.iter().count(), 31);
+        for pattern in registry.iter().filter(|p| p.category != Gun) {
+            let period = match pattern.period {
+                Some(period) => period as usize,
+                None => continue,
+            };
+            let (start, shape) = run(pattern, 0);
+            assert_eq!(shape, pattern.cells, "{}", pattern.name);
+            for generation in 1..period {
+                assert_ne!(run(pattern, generation).1, shape, "{}", pattern.name);
+            }
+            let (end, repeated) = run(pattern, period);
+            assert_eq!(repeated, shape, "{}", pattern.name);
+
+            let moved = start.0.abs_diff(end.0).max(start.1.abs_diff(end.1));
+            match pattern.speed {
+                // c/n covers one cell every n generations
+                Some(speed) => {
+                    let n: usize = speed.trim_start_matches("c/").parse().unwrap();
+                    assert_eq!(moved * n, period, "{}", pattern.name);
+                }
+                None => assert_eq!(moved, 0, "{}", pattern.name),
+            }
+        }
+    }
+
+    #[test]
+    fn pattern_registry_gun() {
+        // The gun sends out a glider every 30 generations
+        let registry = PatternRegistry::default();
+        let gun = registry.get("gosper_glider_gun").unwrap();
+        assert_eq!(gun.size(), (36, 9));
+        let start = run(gun, 0).1.len();
+        assert_eq!(run(gun, 30).1.len(), start + 5);
+        assert_eq!(run(gun, 60).1.len(), start + 10);
+    }
+
+    #[test]
+    fn pattern_registry_search() {
+        let mut registry = PatternRegistry::default();
+        assert_eq!(registry.get("Glider").unwrap().speed, Some("c/4"));
+        assert!(registry.get("glide").is_none());
+
+        let names = |found: Vec<&PatternInfo>| found.iter().map(|p| p.name).collect::<Vec<_>>();
+        assert_eq!(
+            names(registry.search("GLIDER")),
+            vec!["glider", "gosper_glider_gun"]
+        );
+        assert_eq!(
+            names(registry.search("spaceship")),
+            vec![
+                "glider",
+                "lightweight_spaceship",
+                "middleweight_spaceship",
+                "heavyweight_spaceship"
+            ]
+        );
+        assert_eq!(registry.search("still_life").len(), 9);
+        assert!(registry.search("nothing").is_empty());
+
+        registry.add(PatternInfo::new("glider", Oscillator, Some(2), None, "###"));
+        assert_eq!(registry.search("glider").len(), 2);
+        assert_eq!(
+            registry.get("glider").unwrap().cells,
+            vec![(0, 0), (1, 0), (2, 0)]
+        );
+    }
+}
@@ -0,0 +1,6 @@
+// Front ends that draw a board for people to watch.
+
+pub mod sparkline;
+// crossterm isn't built for the browser, even with the feature on
+#[cfg(all(feature = "interactive", not(target_arch = "wasm32")))]
+pub mod tui;
@@ -0,0 +1,107 @@
+// A one-line graph of the population over the last few generations,
+// for seeing at a glance whether a soup is still busy or has settled.
+
+use std::collections::VecDeque;
+
+// Bars from lowest to highest
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+// The same, for terminals without the block characters
+const ASCII_BARS: [char; 8] = ['_', '.', '-', '~', '=', '+', '*', '#'];
+
+// The most recent generations' populations, and the entropy of the
+// board at each, dropping the oldest once `capacity` are held
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopulationHistory {
+    populations: VecDeque<(usize, f64)>,
+    capacity: usize,
+}
+
+impl PopulationHistory {
+    pub fn new(capacity: usize) -> PopulationHistory {
+        PopulationHistory {
+            populations: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn record(&mut self, population: usize, entropy: f64) {
+        if self.populations.len() == self.capacity {
+            self.populations.pop_front();
+        }
+        if self.capacity > 0 {
+            self.populations.push_back((population, entropy));
+        }
+    }
+
+    pub fn recent(&self, count: usize) -> Vec<usize> {
+        // Up to `count` of the latest populations, oldest first
+        let skip = self.populations.len().saturating_sub(count);
+        self.populations
+            .iter()
+            .skip(skip)
+            .map(|&(population, _)| population)
+            .collect()
+    }
+
+    pub fn recent_entropy(&self, count: usize) -> Vec<f64> {
+        // Up to `count` of the latest entropies, oldest first
+        let skip = self.populations.len().saturating_sub(count);
+        self.populations
+            .iter()
+            .skip(skip)
+            .map(|&(_, entropy)| entropy)
+            .collect()
+    }
+}
+
+pub fn sparkline(populations: &[usize], unicode: bool) -> String {
+    // One bar per population, scaled so the smallest in the window is
+    // the lowest bar and the largest the highest. A flat window is all
+    // low bars.
+
+    let bars = if unicode { &BARS } else { &ASCII_BARS };
+    let min = populations.iter().copied().min().unwrap_or(0);
+    let max = populations.iter().copied().max().unwrap_or(0);
+    populations
+        .iter()
+        .map(|&population| {
+            if max == min {
+                bars[0]
+            } else {
+                bars[(population - min) * (bars.len() - 1) / (max - min)]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_bars() {
+        assert_eq!(sparkline(&[0, 1, 2, 3, 4, 5, 6, 7], true), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[7, 6, 5, 4, 3, 2, 1, 0], false), "#*+=~-._");
+        // Scaled to the window rather than to zero
+        assert_eq!(sparkline(&[1000, 1020, 1030, 1070], true), "▁▃▄█");
+        assert_eq!(sparkline(&[40, 40, 40], true), "▁▁▁");
+        assert_eq!(sparkline(&[], true), "");
+    }
+
+    #[test]
+    fn sparkline_history() {
+        let mut history = PopulationHistory::new(3);
+        for population in 1..=5 {
+            history.record(population, population as f64 / 10.0);
+        }
+        assert_eq!(history.recent(10), vec![3, 4, 5]);
+        assert_eq!(history.recent(2), vec![4, 5]);
+        assert_eq!(history.recent(0), Vec::<usize>::new());
+        assert_eq!(history.recent_entropy(2), vec![0.4, 0.5]);
+
+        let mut history = PopulationHistory::new(0);
+        history.record(1, 1.0);
+        assert_eq!(history.recent(1), Vec::<usize>::new());
+        assert_eq!(history.recent_entropy(1), Vec::<f64>::new());
+    }
+}
@@ -0,0 +1,482 @@
+// Terminal view drawn with ratatui: the board, with the status lines,
+// a sparkline of the population and the control hints laid out
+// beneath it.
+
+use std::{io, panic};
+
+use crossterm::{
+    cursor,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::Backend,
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::bar,
+    widgets::{Block, Borders, Paragraph, Sparkline, Widget},
+    Terminal,
+};
+
+use crate::{
+    board::{ALIVE_GLYPH, DEAD_GLYPH},
+    life::Life,
+    render::sparkline::PopulationHistory,
+};
+
+// Number of generations whose population is kept for the sparkline
+const POPULATION_HISTORY: usize = 512;
+
+// Number of cells along each side of a character at each zoom level
+pub const MIN_ZOOM: u8 = 1;
+pub const MAX_ZOOM: u8 = 2;
+
+// Characters for a 2x2 block of cells, indexed by which of them are
+// alive: 1 for the top left, 2 top right, 4 bottom left, 8 bottom right
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+// Sparkline bars for terminals without the block characters, matching
+// those of the text sparkline
+const ASCII_BARS: bar::Set = bar::Set {
+    full: "#",
+    seven_eighths: "*",
+    three_quarters: "+",
+    five_eighths: "=",
+    half: "~",
+    three_eighths: "-",
+    one_quarter: ".",
+    one_eighth: "_",
+    empty: " ",
+};
+
+// Puts the terminal into raw mode on the alternate screen, with mouse
+// events turned on, and back again when dropped. Also restores it before a panic message is
+// printed, so the message isn't lost with the alternate screen.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    pub fn new() -> io::Result<TerminalGuard> {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            hook(info);
+        }));
+        terminal::enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            cursor::Hide
+        )?;
+        Ok(TerminalGuard { _private: () })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    // Errors are ignored, since there's nothing better to do with
+    // them on the way out
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        DisableMouseCapture,
+        LeaveAlternateScreen,
+        cursor::Show
+    );
+}
+
+// The cells in view, drawn `zoom` x `zoom` cells to a character from
+// the top-left corner of the area, and cut off where it ends
+struct BoardView<'a> {
+    life: &'a dyn Life,
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+    cursor: Option<(i64, i64)>,
+    zoom: u8,
+}
+
+impl Widget for BoardView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let zoom = self.zoom as i64;
+        let columns = (self.width + zoom - 1) / zoom;
+        let rows = (self.height + zoom - 1) / zoom;
+        for row in 0..rows.min(area.height as i64) {
+            for column in 0..columns.min(area.width as i64) {
+                // Top-left cell under this character
+                let x = self.origin.0 + column * zoom;
+                let y = self.origin.1 + row * zoom;
+                let glyph = if zoom == 1 {
+                    if self.life.is_alive_at((x, y)) {
+                        ALIVE_GLYPH
+                    } else {
+                        DEAD_GLYPH
+                    }
+                } else {
+                    let corners = [(0, 0), (1, 0), (0, 1), (1, 1)];
+                    let index = corners
+                        .iter()
+                        .enumerate()
+                        .fold(0, |index, (bit, &(dx, dy))| {
+                            let (x, y) = (x + dx, y + dy);
+                            let in_view =
+                                x < self.origin.0 + self.width && y < self.origin.1 + self.height;
+                            index | ((in_view && self.life.is_alive_at((x, y))) as usize) << bit
+                        });
+                    QUADRANTS[index]
+                };
+                let cell = buf.get_mut(area.x + column as u16, area.y + row as u16);
+                cell.set_char(glyph);
+                let under_cursor = self.cursor.is_some_and(|(cx, cy)| {
+                    (x..x + zoom).contains(&cx) && (y..y + zoom).contains(&cy)
+                });
+                if under_cursor {
+                    cell.set_style(Style::default().add_modifier(Modifier::REVERSED));
+                }
+            }
+        }
+    }
+}
+
+pub struct TuiRenderer<B: Backend> {
+    terminal: Terminal<B>,
+    // Stable coordinates of the top-left cell in view, and the size
+    // of the view
+    origin: (i64, i64),
+    width: i64,
+    height: i64,
+    populations: PopulationHistory,
+    // How far into the view the first cell drawn is, when the view
+    // doesn't fit in the terminal
+    scroll: (i64, i64),
+    // Where the board was last drawn, and at what zoom, for working
+    // out which cell a click landed on
+    board_area: Rect,
+    zoom: u8,
+    // Draw the sparkline in ASCII
+    ascii: bool,
+}
+
+impl<B: Backend> TuiRenderer<B> {
+    pub fn new(terminal: Terminal<B>, origin: (i64, i64), width: i64, height: i64) -> Self {
+        TuiRenderer {
+            terminal,
+            origin,
+            width,
+            height,
+            populations: PopulationHistory::new(POPULATION_HISTORY),
+            scroll: (0, 0),
+            board_area: Rect::default(),
+            zoom: MIN_ZOOM,
+            ascii: false,
+        }
+    }
+
+    pub fn set_ascii(&mut self, ascii: bool) {
+        self.ascii = ascii;
+    }
+
+    pub fn record_population(&mut self, population: usize, entropy: f64) {
+        // Adds a generation's population to the sparkline, along with
+        // the board's entropy
+        self.populations.record(population, entropy);
+    }
+
+    pub fn clear(&mut self) -> io::Result<()> {
+        // Makes the next draw repaint the whole terminal, for after it
+        // has been resized
+        self.terminal.clear()
+    }
+
+    pub fn cell_at(&self, column: u16, row: u16) -> Option<(i64, i64)> {
+        // The cell drawn at the given terminal position in the last
+        // frame, or None if the position is off the board. When zoomed
+        // out it's the top-left cell of those sharing the character.
+
+        let area = self.board_area;
+        if !(area.left()..area.right()).contains(&column)
+            || !(area.top()..area.bottom()).contains(&row)
+        {
+            return None;
+        }
+        let zoom = self.zoom as i64;
+        let x = self.origin.0 + self.scroll.0 + (column - area.x) as i64 * zoom;
+        let y = self.origin.1 + self.scroll.1 + (row - area.y) as i64 * zoom;
+        if x < self.origin.0 + self.width && y < self.origin.1 + self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        life: &dyn Life,
+        cursor: Option<(i64, i64)>,
+        zoom: u8,
+        status: &str,
+        hints: &str,
+    ) -> io::Result<()> {
+        // Draws the view of the board at the given zoom level, with
+        // `cursor` highlighted, above the status, which may run over
+        // several lines, the population sparkline and `hints`. The
+        // layout is worked out afresh each time from the terminal's
+        // size, so a resized terminal is picked up on the next draw.
+
+        let zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        let status_lines = status.lines().count().max(1) as u16;
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(status_lines),
+                Constraint::Length(2),
+                Constraint::Length(1),
+            ])
+            .split(self.terminal.size()?);
+        let board_block = self.fit_view(areas[0], zoom, cursor);
+        let first = (self.origin.0 + self.scroll.0, self.origin.1 + self.scroll.1);
+        let view = BoardView {
+            life,
+            origin: first,
+            width: self.width - self.scroll.0,
+            height: self.height - self.scroll.1,
+            cursor,
+            zoom,
+        };
+        let populations = &self.populations;
+        let bars = if self.ascii {
+            ASCII_BARS
+        } else {
+            bar::NINE_LEVELS
+        };
+        let block = Block::default().borders(Borders::ALL);
+        let board_area = block.inner(board_block);
+
+        self.terminal.draw(|frame| {
+            frame.render_widget(block, board_block);
+            frame.render_widget(view, board_area);
+
+            frame.render_widget(Paragraph::new(status), areas[1]);
+
+            // The most recent generations that fit across the screen
+            let recent: Vec<u64> = populations
+                .recent(areas[2].width as usize)
+                .into_iter()
+                .map(|population| population as u64)
+                .collect();
+            frame.render_widget(
+                Sparkline::default()
+                    .data(&recent)
+                    .bar_set(bars)
+                    .style(Style::default().fg(Color::Green)),
+                areas[2],
+            );
+
+            frame.render_widget(
+                Paragraph::new(hints).style(Style::default().fg(Color::DarkGray)),
+                areas[3],
+            );
+        })?;
+        self.board_area = board_area;
+        self.zoom = zoom;
+        Ok(())
+    }
+
+    fn fit_view(&mut self, space: Rect, zoom: u8, cursor: Option<(i64, i64)>) -> Rect {
+        // Where the view goes in `space`, border and all, updating the
+        // scroll to match. Along each axis a view with room to spare is
+        // centred, and one that doesn't fit is cut off, scrolled just
+        // far enough to keep the cursor in sight, but never past its
+        // far edge.
+
+        let zoom = zoom as i64;
+        let fit = |cells: i64, space: u16, scroll: i64, cursor: Option<i64>| {
+            // Offset into the space and length of the view, in
+            // characters, and the new scroll in cells
+            let chars = (cells + zoom - 1) / zoom;
+            let room = space.saturating_sub(2) as i64;
+            if chars <= room {
+                return ((room - chars) as u16 / 2, chars as u16, 0);
+            }
+            let mut first = scroll / zoom;
+            if let Some(cursor) = cursor.map(|cursor| cursor / zoom) {
+                if cursor < first {
+                    first = cursor;
+                } else if cursor >= first + room {
+                    first = cursor - room + 1;
+                }
+            }
+            (0, room as u16, first.clamp(0, chars - room) * zoom)
+        };
+
+        let (left, columns, scroll_x) = fit(
+            self.width,
+            space.width,
+            self.scroll.0,
+            cursor.map(|(x, _)| x - self.origin.0),
+        );
+        let (top, rows, scroll_y) = fit(
+            self.height,
+            space.height,
+            self.scroll.1,
+            cursor.map(|(_, y)| y - self.origin.1),
+        );
+        self.scroll = (scroll_x, scroll_y);
+        Rect::new(
+            space.x + left,
+            space.y + top,
+            (columns + 2).min(space.width),
+            (rows + 2).min(space.height),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use ratatui::backend::TestBackend;
+
+    fn renderer(width: u16, height: u16) -> TuiRenderer<TestBackend> {
+        // Renders a 6x4 view onto a test terminal of the given size
+        let terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        TuiRenderer::new(terminal, (1, 1), 6, 4)
+    }
+
+    fn line(renderer: &TuiRenderer<TestBackend>, y: u16) -> String {
+        let buffer = renderer.terminal.backend().buffer();
+        (0..buffer.area.width)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect()
+    }
+
+    fn glider() -> Board {
+        Board::from_ascii(".#....\n..#...\n###...\n......", '#').unwrap()
+    }
+
+    #[test]
+    fn tui_draw() {
+        let mut renderer = renderer(8, 10);
+        for population in [1, 5, 3].iter() {
+            renderer.record_population(*population, 0.0);
+        }
+        renderer
+            .draw(&glider(), Some((6, 1)), 1, "Paused", "[q] quit")
+            .unwrap();
+
+        assert_eq!(line(&renderer, 0), "┌──────┐");
+        assert_eq!(line(&renderer, 1), "│░█░░░░│");
+        assert_eq!(line(&renderer, 2), "│░░█░░░│");
+        assert_eq!(line(&renderer, 3), "│███░░░│");
+        assert_eq!(line(&renderer, 4), "│░░░░░░│");
+        assert_eq!(line(&renderer, 5), "└──────┘");
+        assert_eq!(line(&renderer, 6).trim_end(), "Paused");
+        assert_eq!(line(&renderer, 9).trim_end(), "[q] quit");
+        // The sparkline is scaled to its tallest bar
+        assert_ne!(line(&renderer, 8).trim_end(), "");
+
+        let buffer = renderer.terminal.backend().buffer();
+        assert!(buffer.get(6, 1).modifier.contains(Modifier::REVERSED));
+        assert!(!buffer.get(5, 1).modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn tui_ascii_sparkline() {
+        let mut renderer = renderer(8, 10);
+        renderer.set_ascii(true);
+        for population in [0, 8].iter() {
+            renderer.record_population(*population, 0.0);
+        }
+        renderer.draw(&glider(), None, 1, "", "").unwrap();
+        assert_eq!(line(&renderer, 7).trim_end(), " #");
+        assert_eq!(line(&renderer, 8).trim_end(), " #");
+    }
+
+    #[test]
+    fn tui_zoom() {
+        // Each character covers a 2x2 block of cells
+        let mut renderer = renderer(8, 8);
+        renderer
+            .draw(&glider(), None, 2, "Paused", "[q] quit")
+            .unwrap();
+        // Three characters wide, centred in the six there's room for
+        assert_eq!(line(&renderer, 1), " │▝▖ │  ");
+        assert_eq!(line(&renderer, 2), " │▀▘ │  ");
+    }
+
+    #[test]
+    fn tui_status_lines() {
+        // Each line of the status gets a row of its own
+        let mut renderer = renderer(12, 11);
+        renderer
+            .draw(&glider(), None, 1, "Generation 4\nPaused", "[q] quit")
+            .unwrap();
+        assert_eq!(line(&renderer, 5), "  └──────┘  ");
+        assert_eq!(line(&renderer, 6).trim_end(), "Generation 4");
+        assert_eq!(line(&renderer, 7).trim_end(), "Paused");
+        assert_eq!(line(&renderer, 10).trim_end(), "[q] quit");
+    }
+
+    #[test]
+    fn tui_cell_at() {
+        let mut renderer = renderer(10, 10);
+        renderer.draw(&glider(), None, 1, "", "").unwrap();
+        // Inside the border, one cell per character, with a spare
+        // column either side
+        assert_eq!(renderer.cell_at(2, 1), Some((1, 1)));
+        assert_eq!(renderer.cell_at(7, 4), Some((6, 4)));
+        assert_eq!(renderer.cell_at(1, 1), None);
+        assert_eq!(renderer.cell_at(8, 1), None);
+        assert_eq!(renderer.cell_at(2, 5), None);
+
+        // Zoomed out, a character stands for the top-left of its cells
+        renderer.draw(&glider(), None, 2, "", "").unwrap();
+        assert_eq!(renderer.cell_at(3, 2), Some((1, 1)));
+        assert_eq!(renderer.cell_at(5, 3), Some((5, 3)));
+        assert_eq!(renderer.cell_at(6, 2), None);
+        assert_eq!(renderer.cell_at(3, 4), None);
+    }
+
+    #[test]
+    fn tui_resize() {
+        let mut renderer = renderer(8, 10);
+        renderer.draw(&glider(), None, 1, "", "").unwrap();
+        assert_eq!(line(&renderer, 1), "│░█░░░░│");
+
+        // Too narrow for the view, which is cut off on the right
+        renderer.terminal.backend_mut().resize(6, 10);
+        renderer.clear().unwrap();
+        renderer.draw(&glider(), None, 1, "", "").unwrap();
+        assert_eq!(line(&renderer, 1), "│░█░░│");
+        assert_eq!(renderer.cell_at(4, 1), Some((4, 1)));
+
+        // Scrolled along to keep the cursor in sight, then back as far
+        // as the view allows once the terminal grows again
+        renderer.draw(&glider(), Some((6, 3)), 1, "", "").unwrap();
+        assert_eq!(line(&renderer, 3), "│█░░░│");
+        assert_eq!(renderer.cell_at(1, 1), Some((3, 1)));
+        renderer.terminal.backend_mut().resize(7, 10);
+        renderer.draw(&glider(), Some((6, 3)), 1, "", "").unwrap();
+        assert_eq!(line(&renderer, 3), "│██░░░│");
+        assert_eq!(renderer.cell_at(1, 1), Some((2, 1)));
+
+        // Centred when there's room to spare
+        renderer.terminal.backend_mut().resize(14, 14);
+        renderer.draw(&glider(), Some((6, 3)), 1, "", "").unwrap();
+        assert_eq!(line(&renderer, 2), "   ┌──────┐   ");
+        assert_eq!(line(&renderer, 5), "   │███░░░│   ");
+        assert_eq!(renderer.cell_at(4, 3), Some((1, 1)));
+    }
+}
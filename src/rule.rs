@@ -0,0 +1,161 @@
+// Birth/survival rules for life-like cellular automata.
+
+use std::{fmt, str::FromStr};
+
+use crate::error::GolError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    // birth[n] is whether a dead cell with n alive neighbours comes
+    // alive, and survival[n] whether an alive cell with n stays alive.
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Rule {
+    // B3/S23, the rule of Conway's Game of Life
+    pub const CONWAY: Rule = Rule {
+        birth: [false, false, false, true, false, false, false, false, false],
+        survival: [false, false, true, true, false, false, false, false, false],
+    };
+
+    // B2/S, where every alive cell dies after one generation
+    pub const SEEDS: Rule = Rule {
+        birth: [false, false, true, false, false, false, false, false, false],
+        survival: [false; 9],
+    };
+
+    pub fn new(birth: &[u32], survival: &[u32]) -> Result<Rule, GolError> {
+        // Builds a rule from the neighbour counts that cause a birth
+        // and the ones that let a cell survive, each from 0 to 8.
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+        for (counts, flags) in [(birth, &mut rule.birth), (survival, &mut rule.survival)] {
+            for &n in counts {
+                match flags.get_mut(n as usize) {
+                    Some(flag) => *flag = true,
+                    None => {
+                        return Err(GolError::InvalidRule(format!(
+                            "neighbour count {} is above 8",
+                            n
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(rule)
+    }
+
+    pub fn next_state(&self, alive: bool, num_adjacent_alive: u32) -> bool {
+        // Whether a cell is alive next generation
+        let flags = if alive { &self.survival } else { &self.birth };
+        flags
+            .get(num_adjacent_alive as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    pub fn births_from_nothing(&self) -> bool {
+        // Whether dead cells with no alive neighbours come alive (B0)
+        self.birth[0]
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        Rule::CONWAY
+    }
+}
+
+impl FromStr for Rule {
+    type Err = GolError;
+
+    fn from_str(s: &str) -> Result<Rule, GolError> {
+        // Parses a rulestring such as "B3/S23", in either order
+        // and either case. "B36/S23" is HighLife.
+
+        let invalid =
+            || GolError::InvalidRule(format!("expected a rule like B3/S23, found {:?}", s));
+        let (mut birth, mut survival) = (None, None);
+        for part in s.trim().split('/') {
+            let mut chars = part.chars();
+            let counts = match chars.next() {
+                Some('B') | Some('b') if birth.is_none() => &mut birth,
+                Some('S') | Some('s') if survival.is_none() => &mut survival,
+                _ => return Err(invalid()),
+            };
+            let digits: Option<Vec<u32>> = chars.map(|c| c.to_digit(10)).collect();
+            *counts = Some(digits.ok_or_else(invalid)?);
+        }
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Rule::new(&birth, &survival),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for n in (0..9).filter(|&n| self.birth[n]) {
+            write!(f, "{}", n)?;
+        }
+        write!(f, "/S")?;
+        for n in (0..9).filter(|&n| self.survival[n]) {
+            write!(f, "{}", n)?;
+        }
+        Ok(())
+    }
+}
+
+// Serialized as a rulestring, such as "B3/S23"
+#[cfg(feature = "serde")]
+impl serde::Serialize for Rule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Rule {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Rule, D::Error> {
+        let rulestring = String::deserialize(deserializer)?;
+        rulestring.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_conway() {
+        let rule = Rule::default();
+        assert_eq!(rule, Rule::new(&[3], &[2, 3]).unwrap());
+        for n in 0..=8 {
+            assert_eq!(rule.next_state(false, n), n == 3);
+            assert_eq!(rule.next_state(true, n), n == 2 || n == 3);
+        }
+        assert!(!rule.births_from_nothing());
+    }
+
+    #[test]
+    fn rule_parse() {
+        assert_eq!("B3/S23".parse::<Rule>().unwrap(), Rule::CONWAY);
+        assert_eq!("s23/b3".parse::<Rule>().unwrap(), Rule::CONWAY);
+
+        let highlife: Rule = "B36/S23".parse().unwrap();
+        assert!(highlife.next_state(false, 6));
+        assert_eq!(highlife.to_string(), "B36/S23");
+
+        // Seeds, where nothing survives
+        assert_eq!("B2/S".parse::<Rule>().unwrap(), Rule::SEEDS);
+        assert_eq!(Rule::SEEDS.to_string(), "B2/S");
+
+        for invalid in ["", "B3", "B3/S23/S2", "B3/B3", "B9/S23", "B3/S2x", "23/3"].iter() {
+            assert!(invalid.parse::<Rule>().is_err(), "{:?} parsed", invalid);
+        }
+    }
+}
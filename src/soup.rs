@@ -0,0 +1,140 @@
+// Soup searching: filling small squares at random, running each until
+// it settles, and seeing what it leaves behind.
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    board::Board,
+    cycle::{Cycle, CycleDetector},
+    extinction::{Extinction, ExtinctionDetector},
+    life::{randomize, Life},
+};
+
+// Generations a soup may run for before it's given up on
+pub const SOUP_GENERATION_CAP: u64 = 10_000;
+
+// Chance of each cell of a soup starting alive
+const SOUP_DENSITY: f64 = 0.5;
+
+// How much bigger than the soup the board it runs on is, on every side,
+// as a multiple of the soup's size
+const SOUP_MARGIN: usize = 2;
+
+// How a soup's run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    // Every cell died
+    Extinct(Extinction),
+    // Settled into still lifes and oscillators
+    Stabilized(Cycle),
+    // Still changing after SOUP_GENERATION_CAP generations
+    Unsettled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunOutcome {
+    // Seed the soup was filled from. soup(seed, size) rebuilds it.
+    pub seed: u64,
+    pub ending: Ending,
+    // Generation the run stopped at
+    pub generation: u64,
+    // Number of cells alive at the end
+    pub population: usize,
+}
+
+pub fn soup(seed: u64, soup_size: usize) -> Board {
+    // A board with a `soup_size` square of random cells in the middle,
+    // with room around it for the soup to spread before reaching the
+    // edges. Cells that reach them die there, so gliders that escape
+    // crash into the edge rather than running forever.
+    let margin = soup_size * SOUP_MARGIN;
+    let mut board = Board::with_size(soup_size + 2 * margin, soup_size + 2 * margin);
+    board.set_history_depth(0);
+    let origin = (margin as i64 + 1, margin as i64 + 1);
+    let size = soup_size as i64;
+    let mut rng = StdRng::seed_from_u64(seed);
+    randomize(&mut board, origin, size, size, SOUP_DENSITY, &mut rng);
+    board
+}
+
+pub fn run_soup(board: &mut Board) -> Ending {
+    // Ticks the board until it dies out, repeats itself, or reaches
+    // SOUP_GENERATION_CAP
+    let mut cycles = CycleDetector::default();
+    let mut extinctions = ExtinctionDetector::new();
+    loop {
+        let generation = board.generation();
+        if let Some(extinction) = extinctions.record(generation, board.population()) {
+            return Ending::Extinct(extinction);
+        }
+        if let Some(cycle) = cycles.record(generation, board.zobrist_hash()) {
+            return Ending::Stabilized(cycle);
+        }
+        if generation >= SOUP_GENERATION_CAP {
+            return Ending::Unsettled;
+        }
+        board.tick();
+    }
+}
+
+pub fn soup_search(trials: usize, soup_size: usize, seed: u64) -> Vec<RunOutcome> {
+    // Runs `trials` soups of `soup_size` x `soup_size` cells. The soups
+    // are seeded from `seed` onwards, so the same arguments always give
+    // the same outcomes.
+    (0..trials as u64)
+        .map(|trial| {
+            let seed = seed.wrapping_add(trial);
+            let mut board = soup(seed, soup_size);
+            let ending = run_soup(&mut board);
+            RunOutcome {
+                seed,
+                ending,
+                generation: board.generation(),
+                population: board.population(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soup_search_reproducible() {
+        let outcomes = soup_search(20, 8, 42);
+        assert_eq!(outcomes.len(), 20);
+        assert_eq!(soup_search(20, 8, 42), outcomes);
+        assert_ne!(soup_search(20, 8, 43), outcomes);
+        // Overlapping searches share the soups they have in common
+        assert_eq!(soup_search(10, 8, 52), outcomes[10..]);
+
+        for outcome in &outcomes {
+            let mut board = soup(outcome.seed, 8);
+            assert_eq!(run_soup(&mut board), outcome.ending);
+            assert_eq!(board.population(), outcome.population);
+            match outcome.ending {
+                Ending::Extinct(extinction) => {
+                    assert_eq!(outcome.population, 0);
+                    assert_eq!(extinction.generation, outcome.generation);
+                }
+                Ending::Stabilized(cycle) => {
+                    assert!(outcome.population > 0);
+                    assert_eq!(cycle.generation + cycle.period, outcome.generation);
+                }
+                Ending::Unsettled => assert_eq!(outcome.generation, SOUP_GENERATION_CAP),
+            }
+        }
+    }
+
+    #[test]
+    fn soup_search_single_cell() {
+        // A lone cell dies straight away, and an empty soup is already
+        // extinct
+        for outcome in soup_search(10, 1, 0) {
+            assert_eq!(outcome.population, 0);
+            assert!(outcome.generation <= 1);
+        }
+        assert!(soup_search(0, 8, 0).is_empty());
+    }
+}
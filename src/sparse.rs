@@ -0,0 +1,254 @@
+// Sparse, effectively unbounded board that only stores its alive cells.
+
+use std::collections::HashSet;
+
+use crate::{
+    board::{ALIVE_GLYPH, DEAD_GLYPH},
+    life::Life,
+};
+
+// A window onto the plane, `width` x `height` cells with its top-left
+// cell at `origin`, which can be moved to follow a pattern around
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub origin: (i64, i64),
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn centered_on(center: (i64, i64), width: usize, height: usize) -> Viewport {
+        // A window with `center` in its middle, or just up and to the
+        // left of it when a side is even
+        Viewport {
+            origin: (
+                center.0 - (width as i64 - 1) / 2,
+                center.1 - (height as i64 - 1) / 2,
+            ),
+            width,
+            height,
+        }
+    }
+
+    pub fn to_screen(&self, coords: (i64, i64)) -> Option<(usize, usize)> {
+        // Zero-based column and row of `coords` in the window, or None
+        // if it's outside
+        let (x, y) = (coords.0 - self.origin.0, coords.1 - self.origin.1);
+        if (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y) {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseBoard {
+    alive: HashSet<(i64, i64)>,
+}
+
+impl SparseBoard {
+    pub fn new() -> SparseBoard {
+        SparseBoard {
+            alive: HashSet::new(),
+        }
+    }
+
+    pub fn count_adjacent_alive(&self, coords: (i64, i64)) -> u32 {
+        let mut num_adjacent_alive = 0;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if (dx, dy) != (0, 0) && self.alive.contains(&(coords.0 + dx, coords.1 + dy)) {
+                    num_adjacent_alive += 1;
+                }
+            }
+        }
+        num_adjacent_alive
+    }
+
+    pub fn bounding_box(&self) -> Option<((i64, i64), (i64, i64))> {
+        // Top-left and bottom-right cells of the smallest rectangle
+        // holding every alive cell, or None if there are none
+        let mut cells = self.alive.iter();
+        let &first = cells.next()?;
+        Some(cells.fold((first, first), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        }))
+    }
+
+    pub fn render_viewport(&self, viewport: &Viewport) -> String {
+        // Draws the cells inside `viewport`, one line per row, with the
+        // same glyphs as `Cell`. Only the alive cells are looked at, so
+        // the window can be anywhere on the plane.
+        let mut rows = vec![vec![DEAD_GLYPH; viewport.width]; viewport.height];
+        for (x, y) in self.alive.iter().filter_map(|&c| viewport.to_screen(c)) {
+            rows[y][x] = ALIVE_GLYPH;
+        }
+        let mut out = String::new();
+        for row in rows {
+            out.extend(row);
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn tick(&mut self) {
+        // Only alive cells and their neighbours can be alive in the next
+        // generation, so those are the only cells that need counting.
+
+        let mut candidates: HashSet<(i64, i64)> = HashSet::new();
+        for &(x, y) in &self.alive {
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    candidates.insert((x + dx, y + dy));
+                }
+            }
+        }
+
+        let next = candidates
+            .into_iter()
+            .filter(|coords| match self.count_adjacent_alive(*coords) {
+                2 => self.alive.contains(coords),
+                3 => true,
+                _ => false,
+            })
+            .collect();
+        self.alive = next;
+    }
+}
+
+impl Life for SparseBoard {
+    fn step(&mut self) {
+        self.tick();
+    }
+
+    fn is_alive_at(&self, coords: (i64, i64)) -> bool {
+        self.alive.contains(&coords)
+    }
+
+    fn set_alive_at(&mut self, coords: (i64, i64), alive: bool) {
+        if alive {
+            self.alive.insert(coords);
+        } else {
+            self.alive.remove(&coords);
+        }
+    }
+
+    fn population(&self) -> usize {
+        self.alive.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::life::{randomize, render};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const GLIDER: [(i64, i64); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    #[test]
+    fn sparse_glider_travels() {
+        let mut board = SparseBoard::new();
+        for coords in GLIDER.iter() {
+            board.set_alive_at(*coords, true);
+        }
+
+        for _ in 0..1000 {
+            board.tick();
+        }
+
+        // A glider moves one cell diagonally every four generations
+        let mut expected = SparseBoard::new();
+        for (x, y) in GLIDER.iter() {
+            expected.set_alive_at((x + 250, y + 250), true);
+        }
+        assert_eq!(board, expected);
+    }
+
+    #[test]
+    fn sparse_glider_period() {
+        let mut board = SparseBoard::new();
+        for coords in GLIDER.iter() {
+            board.set_alive_at(*coords, true);
+        }
+        let start = board.clone();
+
+        // The glider only returns to its own shape after all four
+        // generations, by which time it has moved by (1, 1)
+        for generation in 1..=4 {
+            board.tick();
+            let shifted: HashSet<(i64, i64)> =
+                start.alive.iter().map(|&(x, y)| (x + 1, y + 1)).collect();
+            assert_eq!(board.alive == shifted, generation == 4);
+            assert_ne!(board, start);
+        }
+    }
+
+    #[test]
+    fn sparse_viewport_follows_glider() {
+        let mut board = SparseBoard::new();
+        for coords in GLIDER.iter() {
+            board.set_alive_at(*coords, true);
+        }
+
+        // Centred on the glider's bounding box every generation, the
+        // glider stays in the middle 3x3 of the window while it
+        // travels hundreds of cells
+        for _ in 0..400 {
+            let (min, max) = board.bounding_box().unwrap();
+            let center = ((min.0 + max.0) / 2, (min.1 + max.1) / 2);
+            let viewport = Viewport::centered_on(center, 11, 9);
+            let drawn = board.render_viewport(&viewport);
+            assert_eq!(
+                drawn,
+                render(&board, viewport.origin, 11, 9),
+                "{:?}",
+                viewport
+            );
+            for (y, line) in drawn.lines().enumerate() {
+                for (x, glyph) in line.chars().enumerate() {
+                    if glyph == ALIVE_GLYPH {
+                        assert!((4..=6).contains(&x) && (3..=5).contains(&y));
+                    }
+                }
+            }
+            assert_eq!(drawn.matches(ALIVE_GLYPH).count(), 5);
+            board.tick();
+        }
+        assert_eq!(board.bounding_box(), Some(((100, 100), (102, 102))));
+        assert_eq!(SparseBoard::new().bounding_box(), None);
+    }
+
+    #[test]
+    fn sparse_matches_dense() {
+        let mut dense = Board::new();
+        let mut sparse = SparseBoard::new();
+
+        // Seed the middle of the dense board, far enough from its
+        // edges that the two engines should not diverge
+        randomize(
+            &mut dense,
+            (53, 25),
+            20,
+            20,
+            0.5,
+            &mut StdRng::seed_from_u64(42),
+        );
+        randomize(
+            &mut sparse,
+            (53, 25),
+            20,
+            20,
+            0.5,
+            &mut StdRng::seed_from_u64(42),
+        );
+
+        for _ in 0..50 {
+            assert_eq!(dense.population(), sparse.population());
+            dense.step();
+            sparse.step();
+        }
+    }
+}
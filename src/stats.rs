@@ -0,0 +1,156 @@
+// What happened in each generation, and a CSV log of it for looking
+// at how a soup behaves over a long run.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+// How often rows are flushed to the file, so stopping the program
+// with Ctrl-C loses at most this much of the log
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+const CSV_HEADER: &str = "generation,population,births,deaths,elapsed_ms";
+
+// What a single tick did to the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickStats {
+    // Generation the board has just reached
+    pub generation: u64,
+    // Number of cells that came to life and that died on the way
+    pub births: usize,
+    pub deaths: usize,
+    // Number of alive cells before and after
+    pub population_before: usize,
+    pub population_after: usize,
+}
+
+impl TickStats {
+    pub fn changed(&self) -> usize {
+        // Number of cells that changed state
+        self.births + self.deaths
+    }
+}
+
+pub fn entropy(alive: usize, cells: usize) -> f64 {
+    // Shannon entropy, in bits, of a cell's state when `alive` of
+    // `cells` cells are alive: 1 when half are, falling to 0 when all
+    // or none are. A quickly falling entropy means the board is
+    // settling into something simple.
+    if cells == 0 {
+        return 0.0;
+    }
+    let p = (alive as f64 / cells as f64).min(1.0);
+    if p == 0.0 || p == 1.0 {
+        return 0.0;
+    }
+    -p * p.log2() - (1.0 - p) * (1.0 - p).log2()
+}
+
+pub struct StatsWriter {
+    out: BufWriter<File>,
+    started: Instant,
+    last_flush: Instant,
+    // The first write that failed. Nothing more is written after it.
+    error: Option<io::Error>,
+}
+
+impl StatsWriter {
+    pub fn create(path: &Path) -> io::Result<StatsWriter> {
+        // Creates the file at `path`, replacing any already there, and
+        // writes the header row. Elapsed times are counted from now.
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{}", CSV_HEADER)?;
+        out.flush()?;
+        let now = Instant::now();
+        Ok(StatsWriter {
+            out,
+            started: now,
+            last_flush: now,
+            error: None,
+        })
+    }
+
+    pub fn record(&mut self, stats: &TickStats) {
+        // Adds a row for a generation. A failed write is kept for
+        // finish rather than returned, so a full disk doesn't get in
+        // the way of the simulation.
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.write_row(stats) {
+            self.error = Some(e);
+        }
+    }
+
+    fn write_row(&mut self, stats: &TickStats) -> io::Result<()> {
+        writeln!(
+            self.out,
+            "{},{},{},{},{}",
+            stats.generation,
+            stats.population_after,
+            stats.births,
+            stats.deaths,
+            self.started.elapsed().as_millis()
+        )?;
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.out.flush()?;
+            self.last_flush = Instant::now();
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        // Flushes the rows still buffered, returning the first error
+        // met while writing, if there was one
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.out.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+
+    #[test]
+    fn stats_entropy() {
+        assert_eq!(entropy(50, 100), 1.0);
+        assert_eq!(entropy(0, 100), 0.0);
+        assert_eq!(entropy(100, 100), 0.0);
+        assert_eq!(entropy(0, 0), 0.0);
+        // Symmetric, and more alive cells than cells counts as all
+        assert_eq!(entropy(25, 100), entropy(75, 100));
+        assert!((entropy(25, 100) - 0.8113).abs() < 1e-4);
+        assert_eq!(entropy(150, 100), 0.0);
+    }
+
+    #[test]
+    fn stats_csv() {
+        let path = std::env::temp_dir().join("gameoflife-rs-stats_csv.csv");
+        let mut board = Board::from_ascii(".....\n.....\n.###.\n.....\n.....", '#').unwrap();
+        let mut writer = StatsWriter::create(&path).unwrap();
+        for _ in 0..10 {
+            writer.record(&board.tick());
+        }
+        writer.finish().unwrap();
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        let rows: Vec<Vec<u128>> = lines
+            .map(|line| line.split(',').map(|n| n.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(rows.len(), 10);
+        for (i, row) in rows.iter().enumerate() {
+            // generation, population, births and deaths of a blinker
+            assert_eq!(row[..4], [i as u128 + 1, 3, 2, 2]);
+        }
+        assert!(rows.windows(2).all(|pair| pair[0][4] <= pair[1][4]));
+    }
+}
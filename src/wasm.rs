@@ -0,0 +1,97 @@
+// Bindings for running the dense board in the browser, where
+// JavaScript draws the cells, for example onto a canvas.
+
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+
+#[wasm_bindgen]
+pub struct WasmBoard {
+    board: Board,
+}
+
+#[wasm_bindgen]
+impl WasmBoard {
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: usize, height: usize) -> WasmBoard {
+        // Nothing in the browser can undo, so no history is kept
+        let mut board = Board::with_size(width, height);
+        board.set_history_depth(0);
+        WasmBoard { board }
+    }
+
+    pub fn width(&self) -> usize {
+        self.board.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.height()
+    }
+
+    pub fn tick(&mut self) {
+        self.board.tick();
+    }
+
+    pub fn tick_changes_flat(&mut self) -> Vec<u32> {
+        // Ticks, returning the cells that changed packed as x, y, alive
+        // triples with zero-based coordinates and alive as 0 or 1, so
+        // only those need redrawing
+        self.board
+            .tick_diff()
+            .into_iter()
+            .flat_map(|(x, y, alive)| [x as u32 - 1, y as u32 - 1, alive as u32])
+            .collect()
+    }
+
+    pub fn randomize(&mut self, seed: u32, density: f64) {
+        self.board.reset_random(seed as u64, density);
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, alive: bool) {
+        // Sets the cell at zero-based (x, y), ignoring cells off the board
+        if let Some(cell) = self.board.get_cell_mut((x as usize + 1, y as usize + 1)) {
+            cell.alive = alive;
+        }
+    }
+
+    pub fn live_cells_flat(&self) -> Vec<u32> {
+        // Zero-based coordinates of every alive cell, row by row, packed
+        // as x0, y0, x1, y1, ... so they cross into JavaScript as a
+        // single Uint32Array.
+        let mut cells = Vec::new();
+        for y in 1..=self.board.height() {
+            for x in 1..=self.board.width() {
+                if self.board.get_cell((x, y)).is_some_and(|cell| cell.alive) {
+                    cells.push(x as u32 - 1);
+                    cells.push(y as u32 - 1);
+                }
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wasm_live_cells_flat() {
+        let mut board = WasmBoard::new(5, 5);
+        for &(x, y) in [(2, 1), (2, 2), (2, 3)].iter() {
+            board.set(x, y, true);
+        }
+        board.set(9, 9, true);
+        assert_eq!(board.live_cells_flat(), vec![2, 1, 2, 2, 2, 3]);
+
+        board.tick();
+        assert_eq!(board.live_cells_flat(), vec![1, 2, 2, 2, 3, 2]);
+
+        // Back to vertical: the ends swap places
+        assert_eq!(
+            board.tick_changes_flat(),
+            vec![2, 1, 1, 1, 2, 0, 3, 2, 0, 2, 3, 1]
+        );
+        assert_eq!(board.live_cells_flat(), vec![2, 1, 2, 2, 2, 3]);
+    }
+}